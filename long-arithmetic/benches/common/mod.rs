@@ -0,0 +1,18 @@
+// helpers shared by the benches
+
+use long_arithmetic::biguint::BigUInt;
+
+// a deterministic xorshift operand of exactly `len` limbs
+pub fn operand(seed: u64, len: usize) -> BigUInt {
+    let mut state = seed;
+    let mut limbs: Vec<u64> = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+        .collect();
+    *limbs.last_mut().unwrap() |= 1;
+    BigUInt::from(limbs)
+}
@@ -0,0 +1,91 @@
+// Workloads that mix small and large values. Small numbers of up to
+// `limbs::INLINE_LIMBS` limbs are stored inline, so the first rows mostly
+// measure allocator traffic. Run with `cargo bench --bench mixed`.
+
+mod common;
+
+use common::operand;
+use long_arithmetic::biguint::BigUInt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+type Workload<'a> = Box<dyn Fn() -> usize + 'a>;
+
+// average time of one run of `workload`
+fn measure<T>(mut workload: impl FnMut() -> T) -> Duration {
+    let mut iterations = 0;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(300) || iterations < 3 {
+        black_box(workload());
+        iterations += 1;
+    }
+    start.elapsed() / iterations
+}
+
+// sum of products of one-limb values, all intermediate results fit in two limbs
+fn small_sum(values: &[BigUInt]) -> BigUInt {
+    let mut sum = BigUInt::from(0u64);
+    for pair in values.windows(2) {
+        sum += &pair[0] * &pair[1];
+        sum %= &values[0];
+    }
+    sum
+}
+
+// gcd of every pair of neighbours by Euclid's algorithm
+fn small_gcd(values: &[BigUInt]) -> usize {
+    let mut count = 0;
+    for pair in values.windows(2) {
+        let (mut a, mut b) = (pair[0].clone(), pair[1].clone());
+        while !b.is_zero() {
+            (a, b) = (b.clone(), a % b);
+        }
+        count += a.0.len();
+    }
+    count
+}
+
+fn parse_format(strings: &[String]) -> usize {
+    strings
+        .iter()
+        .map(|s| s.parse::<BigUInt>().unwrap().to_string().len())
+        .sum()
+}
+
+// small factors multiplied into a growing product
+fn factorial(n: u64) -> BigUInt {
+    (1..=n).fold(BigUInt::from(1u64), |acc, k| acc * BigUInt::from(k))
+}
+
+// a large value reduced by many small moduli
+fn large_by_small(large: &BigUInt, moduli: &[BigUInt]) -> usize {
+    moduli.iter().map(|m| (large % m).0.len()).sum()
+}
+
+fn main() {
+    let small: Vec<BigUInt> = (0..1000).map(|i| operand(i + 1, 1)).collect();
+    let small: Vec<BigUInt> = small.iter().map(|x| x >> 1u32).collect();
+    let strings: Vec<String> = small.iter().map(ToString::to_string).collect();
+    let large = operand(7, 1000);
+    let (lhs, rhs) = (operand(8, 2000), operand(9, 2000));
+
+    let workloads: [(&str, Workload); 6] = [
+        (
+            "small sum of products",
+            Box::new(|| small_sum(&small).0.len()),
+        ),
+        ("small gcd", Box::new(|| small_gcd(&small))),
+        ("parse and format", Box::new(|| parse_format(&strings))),
+        ("factorial 3000", Box::new(|| factorial(3000).0.len())),
+        (
+            "large mod small",
+            Box::new(|| large_by_small(&large, &small)),
+        ),
+        ("2000-limb product", Box::new(|| (&lhs * &rhs).0.len())),
+    ];
+
+    println!("{:<24}{:>14}", "workload", "time");
+    for (name, workload) in &workloads {
+        println!("{name:<24}{:>14}", format!("{:.1?}", measure(workload)));
+    }
+}
@@ -0,0 +1,70 @@
+// Compares multiplication algorithms on balanced operands of growing size.
+// Each method is used for the top-level product only, recursive sub-products
+// go through the default dispatch. Run with `cargo bench --bench mul`,
+// the `winner` column shows where each method should be chosen by `MulThresholds`.
+
+mod common;
+
+use common::operand;
+use long_arithmetic::biguint::BigUInt;
+use long_arithmetic::karatsuba::karatsuba_mul;
+use long_arithmetic::mul::schoolbook_mul;
+use long_arithmetic::ntt::ntt_mul;
+use long_arithmetic::toom::toom3_mul;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+type Method = fn(BigUInt, BigUInt) -> BigUInt;
+
+const METHODS: [(&str, Method, usize); 4] = [
+    ("schoolbook", schoolbook_mul, 1024),
+    ("karatsuba", karatsuba_mul, usize::MAX),
+    ("toom3", toom3_mul, usize::MAX),
+    ("ntt", ntt_mul, usize::MAX),
+];
+
+// average time of one multiplication
+fn measure(method: Method, lhs: &BigUInt, rhs: &BigUInt) -> Duration {
+    let mut iterations = 0;
+    let mut total = Duration::ZERO;
+
+    while total < Duration::from_millis(200) || iterations < 3 {
+        let (lhs, rhs) = (lhs.clone(), rhs.clone());
+        let start = Instant::now();
+        black_box(method(black_box(lhs), black_box(rhs)));
+        total += start.elapsed();
+        iterations += 1;
+    }
+
+    total / iterations
+}
+
+fn main() {
+    print!("{:>8}", "limbs");
+    for (name, _, _) in METHODS {
+        print!("{name:>14}");
+    }
+    println!("{:>14}", "winner");
+
+    // powers of two and the midpoints 1.5 * 2^k, past the NTT crossover
+    let sizes = (2..=16).flat_map(|k| [1 << k, 3 << (k - 1)]);
+    for size in sizes.take_while(|&size| size <= 1 << 16) {
+        let lhs = operand(size as u64, size);
+        let rhs = operand(size as u64 + 1, size);
+
+        print!("{size:>8}");
+        let mut best: Option<(&str, Duration)> = None;
+        for (name, method, limit) in METHODS {
+            if size > limit {
+                print!("{:>14}", "-");
+                continue;
+            }
+            let time = measure(method, &lhs, &rhs);
+            print!("{:>14}", format!("{time:.1?}"));
+            if best.is_none_or(|(_, t)| time < t) {
+                best = Some((name, time));
+            }
+        }
+        println!("{:>14}", best.unwrap().0);
+    }
+}
@@ -1,42 +1,52 @@
 use super::{BigUInt, Pow};
 
-use std::ops::{Add, Mul, Neg, Shl, Shr, Sub};
+use std::ops::{AddAssign, Mul, Neg, ShlAssign, ShrAssign, SubAssign};
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub enum Sign {
     Plus,
     Minus,
 }
 
-// invariant - zero is always positive
-#[derive(PartialEq, Eq, Clone, Debug)]
+// invariant - zero is always positive, so the derived `Hash` agrees with `Eq`
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
 pub struct BigInt {
     pub inner: BigUInt,
     pub sign: Sign,
 }
 
+impl Default for BigInt {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
 impl BigInt {
+    pub const ZERO: Self = Self {
+        inner: BigUInt::ZERO,
+        sign: Sign::Plus,
+    };
+    pub const ONE: Self = Self {
+        inner: BigUInt::ONE,
+        sign: Sign::Plus,
+    };
+
     pub fn div_rem(self, rhs: Self) -> (Self, Self) {
-        let (div, rem) = self.inner.div_rem(rhs.inner);
-
-        match (self.sign, rhs.sign) {
-            (Sign::Plus, Sign::Plus) => (
-                BigInt::from((div, Sign::Plus)),
-                BigInt::from((rem, Sign::Plus)),
-            ),
-            (Sign::Plus, Sign::Minus) => (
-                BigInt::from((div, Sign::Minus)).fix_zero(),
-                BigInt::from((rem, Sign::Plus)),
-            ),
-            (Sign::Minus, Sign::Plus) => (
-                BigInt::from((div, Sign::Minus)).fix_zero(),
-                BigInt::from((rem, Sign::Minus)).fix_zero(),
-            ),
-            (Sign::Minus, Sign::Minus) => (
-                BigInt::from((div, Sign::Plus)),
-                BigInt::from((rem, Sign::Minus)).fix_zero(),
-            ),
-        }
+        self.div_rem_ref(&rhs)
+    }
+    // truncated division, the remainder has the sign of the dividend
+    pub(crate) fn div_rem_ref(&self, rhs: &Self) -> (Self, Self) {
+        let (div, rem) = crate::div::div_rem(&self.inner, &rhs.inner);
+        let div_sign = if self.sign == rhs.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+
+        (
+            BigInt::from((div, div_sign)).fix_zero(),
+            BigInt::from((rem, self.sign)).fix_zero(),
+        )
     }
     pub fn fix_zero(self) -> Self {
         if self.inner.is_zero() {
@@ -47,23 +57,29 @@ impl BigInt {
     }
 }
 
-impl Shr<u32> for BigInt {
-    type Output = Self;
-    fn shr(self, rhs: u32) -> Self::Output {
-        BigInt {
-            inner: self.inner >> rhs,
-            sign: self.sign,
+// arithmetic shift of the two's complement form like in Python, so right shift
+// of negative numbers rounds towards negative infinity: -5 >> 1 == -3
+impl ShrAssign<u32> for BigInt {
+    fn shr_assign(&mut self, rhs: u32) {
+        // a negative number loses one bits when the magnitude has a one below `rhs`
+        let inexact = self.sign == Sign::Minus
+            && self
+                .inner
+                .trailing_zeros()
+                .is_some_and(|zeros| zeros < u64::from(rhs));
+        self.inner >>= rhs;
+        if inexact {
+            self.inner += 1;
+        }
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
         }
     }
 }
 
-impl Shl<u32> for BigInt {
-    type Output = Self;
-    fn shl(self, rhs: u32) -> Self::Output {
-        BigInt {
-            inner: self.inner << rhs,
-            sign: self.sign,
-        }
+impl ShlAssign<u32> for BigInt {
+    fn shl_assign(&mut self, rhs: u32) {
+        self.inner <<= rhs;
     }
 }
 
@@ -90,44 +106,46 @@ impl Neg for BigInt {
     }
 }
 
-impl Add for BigInt {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        match (self.sign, rhs.sign) {
-            (Sign::Plus, Sign::Plus) => BigInt::from((self.inner + rhs.inner, Sign::Plus)),
-            (Sign::Minus, Sign::Minus) => BigInt::from((self.inner + rhs.inner, Sign::Minus)),
-            (Sign::Plus, Sign::Minus) => {
-                if self.inner >= rhs.inner {
-                    BigInt::from((self.inner - rhs.inner, Sign::Plus))
-                } else {
-                    BigInt::from((rhs.inner - self.inner, Sign::Minus))
-                }
-            }
-            (Sign::Minus, Sign::Plus) => rhs + self,
+impl BigInt {
+    // self += sign * rhs, in place
+    fn add_signed(&mut self, rhs: &BigUInt, sign: Sign) {
+        if self.sign == sign {
+            self.inner += rhs;
+        } else if self.inner >= *rhs {
+            self.inner -= rhs;
+        } else {
+            self.inner.sub_from(rhs);
+            self.sign = sign;
+        }
+
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
         }
     }
 }
 
-impl Sub for BigInt {
-    type Output = Self;
+impl AddAssign<&BigInt> for BigInt {
+    fn add_assign(&mut self, rhs: &BigInt) {
+        self.add_signed(&rhs.inner, rhs.sign);
+    }
+}
 
-    #[allow(clippy::suspicious_arithmetic_impl)]
-    fn sub(self, rhs: Self) -> Self::Output {
-        self + rhs.neg()
+impl SubAssign<&BigInt> for BigInt {
+    fn sub_assign(&mut self, rhs: &BigInt) {
+        self.add_signed(&rhs.inner, -rhs.sign);
     }
 }
 
-impl Mul for BigInt {
-    type Output = Self;
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
 
-    // quadratic multiplication
-    fn mul(self, rhs: Self) -> Self::Output {
+    fn mul(self, rhs: &BigInt) -> BigInt {
         let sign = if self.sign == rhs.sign {
             Sign::Plus
         } else {
             Sign::Minus
         };
-        Self::from((self.inner * rhs.inner, sign))
+        BigInt::from((&self.inner * &rhs.inner, sign)).fix_zero()
     }
 }
 
@@ -135,7 +153,11 @@ impl Pow<u64> for BigInt {
     type Output = Self;
 
     fn pow(self, rhs: u64) -> Self::Output {
-        let sign = if rhs % 2 == 0 { Sign::Plus } else { self.sign };
+        let sign = if rhs.is_multiple_of(2) {
+            Sign::Plus
+        } else {
+            self.sign
+        };
 
         let inner = self.inner.pow(rhs);
 
@@ -143,6 +165,110 @@ impl Pow<u64> for BigInt {
     }
 }
 
+// quotient and remainder for every way of rounding the quotient,
+// the remainder is always `self - quotient * rhs`
+pub trait DivRem<RHS = Self> {
+    type Output;
+
+    // towards zero, the remainder has the sign of `self`
+    fn div_rem_trunc(self, rhs: RHS) -> (Self::Output, Self::Output);
+    // towards negative infinity, the remainder has the sign of `rhs`
+    fn div_rem_floor(self, rhs: RHS) -> (Self::Output, Self::Output);
+    // towards positive infinity, the remainder has the opposite sign of `rhs`
+    fn div_rem_ceil(self, rhs: RHS) -> (Self::Output, Self::Output);
+    // the remainder is never negative
+    fn div_rem_euclid(self, rhs: RHS) -> (Self::Output, Self::Output);
+}
+
+impl DivRem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div_rem_trunc(self, rhs: &BigInt) -> (BigInt, BigInt) {
+        self.div_rem_ref(rhs)
+    }
+
+    fn div_rem_floor(self, rhs: &BigInt) -> (BigInt, BigInt) {
+        let (mut div, mut rem) = self.div_rem_ref(rhs);
+        if !rem.inner.is_zero() && rem.sign != rhs.sign {
+            div -= &BigInt::from(1);
+            rem += rhs;
+        }
+        (div, rem)
+    }
+
+    fn div_rem_ceil(self, rhs: &BigInt) -> (BigInt, BigInt) {
+        let (mut div, mut rem) = self.div_rem_ref(rhs);
+        if !rem.inner.is_zero() && rem.sign == rhs.sign {
+            div += &BigInt::from(1);
+            rem -= rhs;
+        }
+        (div, rem)
+    }
+
+    fn div_rem_euclid(self, rhs: &BigInt) -> (BigInt, BigInt) {
+        let (mut div, mut rem) = self.div_rem_ref(rhs);
+        if rem.sign == Sign::Minus {
+            match rhs.sign {
+                Sign::Plus => div -= &BigInt::from(1),
+                Sign::Minus => div += &BigInt::from(1),
+            }
+            rem.add_signed(&rhs.inner, Sign::Plus);
+        }
+        (div, rem)
+    }
+}
+
+impl DivRem for BigInt {
+    type Output = BigInt;
+
+    fn div_rem_trunc(self, rhs: BigInt) -> (BigInt, BigInt) {
+        (&self).div_rem_trunc(&rhs)
+    }
+
+    fn div_rem_floor(self, rhs: BigInt) -> (BigInt, BigInt) {
+        (&self).div_rem_floor(&rhs)
+    }
+
+    fn div_rem_ceil(self, rhs: BigInt) -> (BigInt, BigInt) {
+        (&self).div_rem_ceil(&rhs)
+    }
+
+    fn div_rem_euclid(self, rhs: BigInt) -> (BigInt, BigInt) {
+        (&self).div_rem_euclid(&rhs)
+    }
+}
+
+impl BigInt {
+    // like Python's `//`
+    #[must_use]
+    pub fn div_floor(&self, rhs: &Self) -> Self {
+        self.div_rem_floor(rhs).0
+    }
+
+    // like Python's `%`, the result has the sign of `rhs`
+    #[must_use]
+    pub fn mod_floor(&self, rhs: &Self) -> Self {
+        self.div_rem_floor(rhs).1
+    }
+
+    #[must_use]
+    pub fn div_ceil(&self, rhs: &Self) -> Self {
+        self.div_rem_ceil(rhs).0
+    }
+
+    // same as `i128::div_euclid`
+    #[must_use]
+    pub fn div_euclid(&self, rhs: &Self) -> Self {
+        self.div_rem_euclid(rhs).0
+    }
+
+    // same as `i128::rem_euclid`, never negative
+    #[must_use]
+    pub fn rem_euclid(&self, rhs: &Self) -> Self {
+        self.div_rem_euclid(rhs).1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +299,65 @@ mod tests {
         assert_eq!(rem, BigInt::from(-423862836832296285i64));
     }
 
+    fn big(value: i128) -> BigInt {
+        value.to_string().parse().unwrap()
+    }
+
+    #[test]
+    fn rounding_div_rem_test() {
+        let values = [
+            0i128,
+            1,
+            -1,
+            7,
+            -7,
+            3,
+            -3,
+            i64::MAX as i128 * 5 + 3,
+            -(u64::MAX as i128) * 12_345,
+            i128::MAX,
+            i128::MIN + 1,
+        ];
+        for a in values {
+            for b in values.iter().copied().filter(|&b| b != 0) {
+                let (x, y) = (big(a), big(b));
+                let (div, rem) = (a / b, a % b);
+                let floor = if rem != 0 && (rem < 0) != (b < 0) {
+                    (div - 1, rem + b)
+                } else {
+                    (div, rem)
+                };
+                let ceil = if rem != 0 && (rem < 0) == (b < 0) {
+                    (div + 1, rem - b)
+                } else {
+                    (div, rem)
+                };
+
+                assert_eq!((&x).div_rem_trunc(&y), (big(div), big(rem)), "{a} / {b}");
+                assert_eq!(
+                    (&x).div_rem_floor(&y),
+                    (big(floor.0), big(floor.1)),
+                    "{a} / {b}"
+                );
+                assert_eq!(
+                    (&x).div_rem_ceil(&y),
+                    (big(ceil.0), big(ceil.1)),
+                    "{a} / {b}"
+                );
+                assert_eq!(x.div_euclid(&y), big(a.div_euclid(b)), "{a} / {b}");
+                assert_eq!(x.rem_euclid(&y), big(a.rem_euclid(b)), "{a} / {b}");
+                assert_eq!(x.div_floor(&y), big(floor.0), "{a} / {b}");
+                assert_eq!(x.mod_floor(&y), big(floor.1), "{a} / {b}");
+                assert_eq!(x.div_ceil(&y), big(ceil.0), "{a} / {b}");
+            }
+        }
+
+        assert_eq!(
+            BigInt::from(-7).div_rem_euclid(BigInt::from(-2)),
+            (BigInt::from(4), BigInt::from(1))
+        );
+    }
+
     #[test]
     fn add_test() {
         assert_eq!(
@@ -336,5 +521,13 @@ mod tests {
         assert_eq!(num, right);
 
         assert_eq!(left >> 125, right);
+
+        // floor division by a power of two, like Python and the `i128` shift
+        for value in [-5i128, -4, -1, -(1 << 70) - 1, -(1 << 70), 7] {
+            for shift in [0, 1, 2, 64, 70, 71, 200] {
+                let expected = BigInt::from(value >> shift.min(127));
+                assert_eq!(BigInt::from(value) >> shift, expected, "{value} >> {shift}");
+            }
+        }
     }
 }
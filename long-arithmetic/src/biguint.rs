@@ -1,164 +1,122 @@
 use super::helpers::*;
-use itertools::*;
+use super::limbs::Limbs;
 use std::cmp::{Eq, PartialEq};
-use std::ops::{Add, Mul, Shl, Shr, Sub};
+use std::ops::{AddAssign, Mul, ShlAssign, ShrAssign, SubAssign};
 
 // Unsigned arbitrary-precision numbers represented in "little-endian"-like way
 // 0x1_0000_0000_0000_0000 will be represented like vec![0, 1]
 // No leading zeros - 0 is represented by vec![]
-#[derive(Eq, PartialEq, Clone, Debug)]
-pub struct BigUInt(pub Vec<u64>);
+// Up to `limbs::INLINE_LIMBS` limbs are stored without a heap allocation
+#[derive(Eq, PartialEq, Clone, Debug, Default, Hash)]
+pub struct BigUInt(pub Limbs);
 
 impl BigUInt {
+    pub const ZERO: Self = Self(Limbs::new());
+    pub const ONE: Self = Self(Limbs::single(1));
+
     #[must_use]
     pub fn is_zero(&self) -> bool {
         self.0.is_empty()
     }
     #[must_use]
     pub fn divisible_by_2(&self) -> bool {
-        self.0.first().unwrap_or(&0) % 2 == 0
+        self.0.first().unwrap_or(&0).is_multiple_of(2)
     }
     #[must_use]
-    pub fn div_rem(mut self, mut rhs: Self) -> (Self, Self) {
-        match (self.is_zero(), rhs.is_zero()) {
-            (_, true) => panic!("Division by zero"),
-
-            (true, _) => (Self::from(0), Self::from(0)),
-            (_, _) => {
-                let self_size = self.bits_num();
-                let rhs_size = rhs.bits_num();
-
-                if self_size < rhs_size {
-                    // divisor is bigger than dividend
-                    (Self::from(0), self)
-                } else {
-                    let mut ans = Self::from(0);
-
-                    // align divisor and dividend
-                    rhs <<= self_size - rhs_size;
-                    debug_assert_eq!(self.bits_num(), rhs.bits_num());
-
-                    let mut i = self_size - rhs_size;
-                    loop {
-                        if self >= rhs {
-                            self -= rhs.clone();
-                            ans.set_bit(i as usize);
-                        }
-                        rhs >>= 1;
-                        if i == 0 {
-                            break;
-                        }
-                        i -= 1;
-                    }
-
-                    (ans, self)
-                }
-            }
-        }
+    pub fn div_rem(self, rhs: Self) -> (Self, Self) {
+        crate::div::div_rem(&self, &rhs)
     }
-    fn bits_num(&self) -> u32 {
+    // fast path for single-limb divisors
+    #[must_use]
+    pub fn div_rem_u64(mut self, rhs: u64) -> (Self, u64) {
+        let rem = crate::div::div_rem_limb_in_place(&mut self.0, rhs);
+        (self, rem)
+    }
+    // cheaper than `self * self`, products of distinct limbs are computed once
+    #[must_use]
+    pub fn square(&self) -> Self {
+        crate::mul::square(&self.0)
+    }
+    pub(crate) fn bits_num(&self) -> u32 {
         self.0.last().map_or(0, |last| {
             let rest = 64 - last.leading_zeros();
             (self.0.len() as u32 - 1) * 64 + rest
         })
     }
-    fn set_bit(&mut self, n: usize) {
-        let (sect, bit) = (n / 64, n % 64);
-        if self.0.len() < sect + 1 {
-            self.0.extend(vec![0; sect + 1 - self.0.len()]);
-        }
-        self.0[sect] |= 1 << bit;
-    }
 }
 
-impl Shr<u32> for BigUInt {
-    type Output = Self;
-    fn shr(self, rhs: u32) -> Self::Output {
+impl ShrAssign<u32> for BigUInt {
+    fn shr_assign(&mut self, rhs: u32) {
         let (shamt64, rem) = (rhs / 64, rhs % 64);
-        let tmp = shr_n64(&self, shamt64 as usize);
+        let tmp = shr_n64(BigUInt(std::mem::take(&mut self.0)), shamt64 as usize);
 
-        shr64(tmp, rem)
+        *self = shr64(tmp, rem);
     }
 }
 
-impl Shl<u32> for BigUInt {
-    type Output = Self;
-    fn shl(self, rhs: u32) -> Self::Output {
+impl ShlAssign<u32> for BigUInt {
+    fn shl_assign(&mut self, rhs: u32) {
         let (shamt64, rem) = (rhs / 64, rhs % 64);
-        let tmp = shl_n64(self, shamt64 as usize);
+        let tmp = shl_n64(BigUInt(std::mem::take(&mut self.0)), shamt64 as usize);
 
-        shl64(tmp, rem)
+        *self = shl64(tmp, rem);
     }
 }
 
-impl Add for BigUInt {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        let (mut inner, carry) = self
-            .0
-            .iter()
-            .zip_longest(rhs.0.iter())
-            .map(|x| match x {
-                EitherOrBoth::Both(a, b) => (*a, *b),
-                EitherOrBoth::Left(a) | EitherOrBoth::Right(a) => (*a, 0),
-            })
-            .fold((Vec::new(), false), |(mut v, carry), (a, b)| {
-                let (tmp, carry) = a.carrying_add(b, carry);
-
-                v.push(tmp);
-                (v, carry)
-            });
-
-        if carry {
-            inner.push(1);
-        }
-
-        Self(inner)
+impl AddAssign<&BigUInt> for BigUInt {
+    fn add_assign(&mut self, rhs: &BigUInt) {
+        add_shifted(self, &rhs.0, 0);
     }
 }
 
-impl Sub for BigUInt {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        assert!(self >= rhs, "Cannot subtract lower number from bigger");
-
-        let (mut inner, borrow) = self
-            .0
-            .iter()
-            .zip(rhs.0.iter().chain(std::iter::repeat(&0)))
-            .fold((Vec::new(), false), |(mut v, borrow), (a, b)| {
-                let (tmp, borrow) = a.borrowing_sub(*b, borrow);
+impl SubAssign<&BigUInt> for BigUInt {
+    fn sub_assign(&mut self, rhs: &BigUInt) {
+        assert!(*self >= *rhs, "Cannot subtract lower number from bigger");
 
-                v.push(tmp);
-                (v, borrow)
-            });
+        let (head, tail) = self.0.split_at_mut(rhs.0.len());
+        let mut borrow = false;
+        for (a, &b) in head.iter_mut().zip(&rhs.0) {
+            (*a, borrow) = a.borrowing_sub(b, borrow);
+        }
+        for a in tail {
+            if !borrow {
+                break;
+            }
+            (*a, borrow) = a.overflowing_sub(1);
+        }
 
-        assert!(!borrow, "Cannot subtract lower number from bigger");
+        while self.0.last() == Some(&0) {
+            let _ = self.0.pop();
+        }
+    }
+}
 
-        while inner.last() == Some(&0) {
-            let _ = inner.pop();
+impl BigUInt {
+    // self = lhs - self, reusing self's allocation
+    pub(crate) fn sub_from(&mut self, lhs: &BigUInt) {
+        assert!(*lhs >= *self, "Cannot subtract lower number from bigger");
+
+        self.0.resize(lhs.0.len(), 0);
+        let mut borrow = false;
+        for (a, &b) in self.0.iter_mut().zip(&lhs.0) {
+            (*a, borrow) = b.borrowing_sub(*a, borrow);
         }
 
-        Self(inner)
+        while self.0.last() == Some(&0) {
+            let _ = self.0.pop();
+        }
     }
 }
 
-impl Mul for BigUInt {
-    type Output = Self;
+impl Mul<&BigUInt> for &BigUInt {
+    type Output = BigUInt;
 
-    // quadratic multiplication
-    fn mul(self, rhs: Self) -> Self::Output {
-        let lhs = self.0.into_iter().enumerate();
-        let rhs = rhs.0.into_iter().enumerate();
-
-        lhs.cartesian_product(rhs)
-            .map(|((rshift, rhs), (lshift, lhs))| {
-                let shamt = rshift + lshift;
-                let tmp = mul_helper(lhs, rhs);
-                shl_n64(tmp, shamt)
-            })
-            .fold(Self::from(0), |acc, n| acc + n)
+    // algorithm is chosen by operands' sizes, see `mul::MulThresholds`
+    fn mul(self, rhs: &BigUInt) -> BigUInt {
+        if std::ptr::eq(self, rhs) {
+            return self.square();
+        }
+        crate::mul::mul(&self.0, &rhs.0)
     }
 }
 
@@ -174,16 +132,16 @@ impl Pow<u64> for BigUInt {
 
     fn pow(mut self, mut rhs: u64) -> Self::Output {
         if rhs == 0 {
-            Self::from(1)
+            Self::from(1u64)
         } else {
-            let mut buff = Self::from(1);
+            let mut buff = Self::from(1u64);
 
             while rhs > 1 {
-                if rhs % 2 != 0 {
-                    buff *= self.clone();
+                if !rhs.is_multiple_of(2) {
+                    buff *= &self;
                     rhs -= 1;
                 }
-                self *= self.clone();
+                self = self.square();
                 rhs /= 2;
             }
 
@@ -198,13 +156,13 @@ mod tests {
 
     #[test]
     fn is_zero_test() {
-        let num = BigUInt::from(0);
+        let num = BigUInt::from(0u64);
         assert!(num.is_zero());
 
-        let num = BigUInt::from(128);
+        let num = BigUInt::from(128u64);
         assert!(!num.is_zero());
 
-        let num = BigUInt::from(127);
+        let num = BigUInt::from(127u64);
         assert!(!num.is_zero());
 
         let num = BigUInt::from(vec![0, 0, 0, 123]);
@@ -213,10 +171,10 @@ mod tests {
 
     #[test]
     fn divisible_by_2_test() {
-        let num = BigUInt::from(0);
+        let num = BigUInt::from(0u64);
         assert!(num.divisible_by_2());
 
-        let num = BigUInt::from(12_345_678);
+        let num = BigUInt::from(12_345_678u64);
         assert!(num.divisible_by_2());
 
         let num = BigUInt::from(vec![12_345_678, 123, 456, 789]);
@@ -225,13 +183,13 @@ mod tests {
 
     #[test]
     fn bits_num_test() {
-        let num = BigUInt::from(0);
+        let num = BigUInt::from(0u64);
         assert_eq!(num.bits_num(), 0);
 
-        let num = BigUInt::from(128);
+        let num = BigUInt::from(128u64);
         assert_eq!(num.bits_num(), 8);
 
-        let num = BigUInt::from(127);
+        let num = BigUInt::from(127u64);
         assert_eq!(num.bits_num(), 7);
 
         let num = BigUInt::from(vec![0, 0, 0, 123]);
@@ -240,11 +198,11 @@ mod tests {
 
     #[test]
     fn set_bit_test() {
-        let mut num = BigUInt::from(0);
+        let mut num = BigUInt::from(0u64);
         num.set_bit(130);
         assert_eq!(num, BigUInt::from(vec![0, 0, 4]));
 
-        let mut num = BigUInt::from(128);
+        let mut num = BigUInt::from(128u64);
         num.set_bit(130);
         assert_eq!(num, BigUInt::from(vec![128, 0, 4]));
 
@@ -256,55 +214,61 @@ mod tests {
         num.set_bit(131);
         assert_eq!(num, BigUInt::from(vec![128, 0, 8]));
 
-        let mut num = BigUInt::from(128);
+        let mut num = BigUInt::from(128u64);
         num.set_bit(4);
-        assert_eq!(num, BigUInt::from(144));
+        assert_eq!(num, BigUInt::from(144u64));
 
-        let mut num = BigUInt::from(0);
+        let mut num = BigUInt::from(0u64);
         num.set_bit(0);
-        assert_eq!(num, BigUInt::from(1));
+        assert_eq!(num, BigUInt::from(1u64));
 
-        let mut num = BigUInt::from(0);
+        let mut num = BigUInt::from(0u64);
         num.set_bit(1);
-        assert_eq!(num, BigUInt::from(2));
+        assert_eq!(num, BigUInt::from(2u64));
     }
 
     #[test]
     fn div_rem_test() {
-        let (div, rem) = BigUInt::from(0).div_rem(BigUInt::from(1));
-        assert_eq!(div, BigUInt::from(0));
-        assert_eq!(rem, BigUInt::from(0));
+        let (div, rem) = BigUInt::from(0u64).div_rem(BigUInt::from(1u64));
+        assert_eq!(div, BigUInt::from(0u64));
+        assert_eq!(rem, BigUInt::from(0u64));
 
-        let (div, rem) = BigUInt::from(127).div_rem(BigUInt::from(2));
-        assert_eq!(div, BigUInt::from(63));
-        assert_eq!(rem, BigUInt::from(1));
+        let (div, rem) = BigUInt::from(127u64).div_rem(BigUInt::from(2u64));
+        assert_eq!(div, BigUInt::from(63u64));
+        assert_eq!(rem, BigUInt::from(1u64));
 
-        let (div, rem) = BigUInt::from(122).div_rem(BigUInt::from(3));
-        assert_eq!(div, BigUInt::from(40));
-        assert_eq!(rem, BigUInt::from(2));
+        let (div, rem) = BigUInt::from(122u64).div_rem(BigUInt::from(3u64));
+        assert_eq!(div, BigUInt::from(40u64));
+        assert_eq!(rem, BigUInt::from(2u64));
 
-        let (div, rem) = BigUInt::from(123456).div_rem(BigUInt::from(47));
-        assert_eq!(div, BigUInt::from(2626));
-        assert_eq!(rem, BigUInt::from(34));
+        let (div, rem) = BigUInt::from(123456u64).div_rem(BigUInt::from(47u64));
+        assert_eq!(div, BigUInt::from(2626u64));
+        assert_eq!(rem, BigUInt::from(34u64));
 
         let (div, rem) = "1234567891011121314151617181920"
             .parse::<BigUInt>()
             .unwrap()
-            .div_rem(BigUInt::from(456789101112131415));
-        assert_eq!(div, BigUInt::from(2702708729269));
-        assert_eq!(rem, BigUInt::from(423862836832296285));
+            .div_rem(BigUInt::from(456789101112131415u64));
+        assert_eq!(div, BigUInt::from(2702708729269u64));
+        assert_eq!(rem, BigUInt::from(423862836832296285u64));
     }
 
     #[test]
     fn add_test() {
-        assert_eq!(BigUInt::from(0) + 123_456_789, BigUInt::from(123_456_789));
-        assert_eq!(BigUInt::from(3) + 123_456_789, BigUInt::from(123_456_792));
+        assert_eq!(
+            BigUInt::from(0u64) + 123_456_789,
+            BigUInt::from(123_456_789u64)
+        );
+        assert_eq!(
+            BigUInt::from(3u64) + 123_456_789,
+            BigUInt::from(123_456_792u64)
+        );
         assert_eq!(
             BigUInt::from(u64::MAX) + BigUInt::from(u64::MAX),
             BigUInt::from(vec![u64::MAX - 1, 1])
         );
         assert_eq!(
-            BigUInt::from(0) + BigUInt::from(vec![123, 456, 789]),
+            BigUInt::from(0u64) + BigUInt::from(vec![123, 456, 789]),
             BigUInt::from(vec![123, 456, 789])
         );
         assert_eq!(
@@ -312,7 +276,7 @@ mod tests {
             BigUInt::from(vec![1110, 1110, 1110])
         );
         assert_eq!(
-            BigUInt::from(vec![u64::MAX, u64::MAX, u64::MAX]) + BigUInt::from(1),
+            BigUInt::from(vec![u64::MAX, u64::MAX, u64::MAX]) + BigUInt::from(1u64),
             BigUInt::from(vec![0, 0, 0, 1])
         );
         assert_eq!(
@@ -324,12 +288,12 @@ mod tests {
     #[test]
     fn sub_test() {
         assert_eq!(
-            BigUInt::from(123_456_789) - BigUInt::from(0),
-            BigUInt::from(123_456_789)
+            BigUInt::from(123_456_789u64) - BigUInt::from(0u64),
+            BigUInt::from(123_456_789u64)
         );
         assert_eq!(
-            BigUInt::from(123_456_789) - BigUInt::from(123_456_789),
-            BigUInt::from(0)
+            BigUInt::from(123_456_789u64) - BigUInt::from(123_456_789u64),
+            BigUInt::from(0u64)
         );
         assert_eq!(
             BigUInt::from(vec![123_456_789, 123_456_789]) - BigUInt::from(vec![9, 123_456_789]),
@@ -344,7 +308,7 @@ mod tests {
             BigUInt::from(vec![0xFFFF_FFFF_FFFF_F79C, 0xFFFF_FFFF_FFFF_FE01, 0x467])
         );
         assert_eq!(
-            BigUInt::from(vec![0, 0, 0, 1]) - BigUInt::from(1),
+            BigUInt::from(vec![0, 0, 0, 1]) - BigUInt::from(1u64),
             BigUInt::from(vec![u64::MAX, u64::MAX, u64::MAX]),
         );
         assert_eq!(
@@ -376,9 +340,15 @@ mod tests {
 
     #[test]
     fn mul_test() {
-        assert_eq!(BigUInt::from(0) * 123_456_789, BigUInt::from(0));
-        assert_eq!(BigUInt::from(1) * 123_456_789, BigUInt::from(123_456_789));
-        assert_eq!(BigUInt::from(3) * 123_456_789, BigUInt::from(370_370_367));
+        assert_eq!(BigUInt::from(0u64) * 123_456_789, BigUInt::from(0u64));
+        assert_eq!(
+            BigUInt::from(1u64) * 123_456_789,
+            BigUInt::from(123_456_789u64)
+        );
+        assert_eq!(
+            BigUInt::from(3u64) * 123_456_789,
+            BigUInt::from(370_370_367u64)
+        );
         assert_eq!(
             BigUInt::from(u64::MAX) * BigUInt::from(u64::MAX),
             BigUInt::from(vec![0x0000_0000_0000_0001, 0xFFFF_FFFF_FFFF_FFFE])
@@ -415,7 +385,7 @@ mod tests {
         let pow8 = BigUInt::from(POW8.to_vec());
         let pow10 = BigUInt::from(POW10.to_vec());
 
-        assert_eq!(original.clone().pow(0), BigUInt::from(1));
+        assert_eq!(original.clone().pow(0), BigUInt::from(1u64));
         assert_eq!(original.clone().pow(1), original);
         assert_eq!(original.clone().pow(2), pow2);
         assert_eq!(original.clone().pow(4), pow4);
@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use long_arithmetic::bigint::{BigInt, Sign};
+use long_arithmetic::biguint::BigUInt;
+use long_arithmetic::{combinatorics, ArithmeticError};
+
+use crate::parser::{self, BinaryOp, Expr, ParseError, Statement};
+
+// results are limited to this many bits, so that a typo like `10^10^10` fails quickly
+// instead of exhausting the memory
+pub const MAX_BITS: u64 = 1 << 28;
+
+// name, arguments, description
+pub const FUNCTIONS: [(&str, &str, &str); 14] = [
+    ("abs", "x", "absolute value"),
+    ("gcd", "a, b", "greatest common divisor"),
+    ("lcm", "a, b", "least common multiple"),
+    ("sqrt", "x", "integer square root"),
+    ("root", "x, n", "integer n-th root"),
+    ("modpow", "b, e, m", "b^e mod m"),
+    ("modinv", "a, m", "inverse of a modulo m"),
+    ("factorial", "n", "n!"),
+    ("binomial", "n, k", "binomial coefficient"),
+    ("fib", "n", "n-th Fibonacci number"),
+    ("isprime", "n", "1 if n is a probable prime, 0 otherwise"),
+    ("nextprime", "n", "smallest prime greater than n"),
+    ("bits", "x", "number of bits of |x|"),
+    ("digits", "x", "number of decimal digits of |x|"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CalcError {
+    Parse(ParseError),
+    DivisionByZero,
+    UnknownVariable(String),
+    UnknownFunction(String),
+    WrongArity {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    // an operand outside of the domain of an operation
+    Domain(String),
+    TooLarge,
+}
+
+impl Display for CalcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalcError::Parse(error) => write!(f, "{error}"),
+            CalcError::DivisionByZero => write!(f, "division by zero"),
+            CalcError::UnknownVariable(name) => write!(f, "unknown variable '{name}'"),
+            CalcError::UnknownFunction(name) => write!(f, "unknown function '{name}'"),
+            CalcError::WrongArity {
+                name,
+                expected,
+                found,
+            } => write!(f, "{name} takes {expected} arguments, {found} given"),
+            CalcError::Domain(message) => write!(f, "{message}"),
+            CalcError::TooLarge => write!(f, "result has more than {MAX_BITS} bits"),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+impl From<ParseError> for CalcError {
+    fn from(error: ParseError) -> Self {
+        CalcError::Parse(error)
+    }
+}
+
+impl From<ArithmeticError> for CalcError {
+    fn from(error: ArithmeticError) -> Self {
+        match error {
+            ArithmeticError::DivisionByZero => CalcError::DivisionByZero,
+            ArithmeticError::TooLarge { .. } => CalcError::TooLarge,
+            ArithmeticError::Underflow => unreachable!("signed arithmetic doesn't underflow"),
+            error => CalcError::Domain(error.to_string()),
+        }
+    }
+}
+
+fn non_negative<'a>(value: &'a BigInt, what: &str) -> Result<&'a BigUInt, CalcError> {
+    match value.sign {
+        Sign::Plus => Ok(&value.inner),
+        Sign::Minus => Err(CalcError::Domain(format!("{what} must not be negative"))),
+    }
+}
+
+// small non-negative argument, like a shift amount or the index of a Fibonacci number
+fn small<T: TryFrom<u64>>(value: &BigInt, what: &str, max: u64) -> Result<T, CalcError> {
+    match u64::try_from(non_negative(value, what)?) {
+        Ok(n) if n <= max => T::try_from(n).map_err(|_| CalcError::TooLarge),
+        _ => Err(CalcError::TooLarge),
+    }
+}
+
+fn check_size(value: BigInt) -> Result<BigInt, CalcError> {
+    if value.bit_length() > MAX_BITS {
+        return Err(CalcError::TooLarge);
+    }
+    Ok(value)
+}
+
+// Evaluates statements over `BigInt`. Division truncates towards zero and the remainder
+// has the sign of the dividend, `>>` rounds towards negative infinity, like the operators
+// of the library. The value of the last statement is also stored in the variable `ans`
+#[derive(Default)]
+pub struct Calculator {
+    variables: HashMap<String, BigInt>,
+}
+
+impl Calculator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // value of the last statement of `line`, `None` if there are no statements.
+    // If a statement fails, the assignments of the earlier ones are undone
+    pub fn execute(&mut self, line: &str) -> Result<Option<BigInt>, CalcError> {
+        let statements = parser::parse(line)?;
+        let mut undo = vec![];
+        let result = self.run(statements, &mut undo);
+        if result.is_err() {
+            for (name, value) in undo.into_iter().rev() {
+                match value {
+                    Some(value) => self.variables.insert(name, value),
+                    None => self.variables.remove(&name),
+                };
+            }
+        }
+        result
+    }
+
+    // `undo` collects the previous values of the assigned variables
+    fn run(
+        &mut self,
+        statements: Vec<Statement>,
+        undo: &mut Vec<(String, Option<BigInt>)>,
+    ) -> Result<Option<BigInt>, CalcError> {
+        let mut last = None;
+        for statement in statements {
+            let value = match statement {
+                Statement::Assign(name, expr) => {
+                    let value = self.eval(&expr)?;
+                    self.assign(name, value.clone(), undo);
+                    value
+                }
+                Statement::Expr(expr) => self.eval(&expr)?,
+            };
+            self.assign("ans".to_string(), value.clone(), undo);
+            last = Some(value);
+        }
+        Ok(last)
+    }
+
+    fn assign(&mut self, name: String, value: BigInt, undo: &mut Vec<(String, Option<BigInt>)>) {
+        let previous = self.variables.insert(name.clone(), value);
+        undo.push((name, previous));
+    }
+
+    pub fn eval(&self, expr: &Expr) -> Result<BigInt, CalcError> {
+        match expr {
+            Expr::Number(n) => Ok(n.clone()),
+            Expr::Variable(name) => self
+                .variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| CalcError::UnknownVariable(name.clone())),
+            Expr::Neg(expr) => Ok(-self.eval(expr)?),
+            Expr::Binary(op, lhs, rhs) => binary(*op, &self.eval(lhs)?, &self.eval(rhs)?),
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call(name, &args)
+            }
+        }
+    }
+}
+
+fn binary(op: BinaryOp, lhs: &BigInt, rhs: &BigInt) -> Result<BigInt, CalcError> {
+    match op {
+        BinaryOp::Add => Ok(lhs + rhs),
+        BinaryOp::Sub => Ok(lhs - rhs),
+        BinaryOp::Mul => check_size(lhs * rhs),
+        BinaryOp::Div => Ok(lhs.checked_div(rhs)?),
+        BinaryOp::Rem => Ok(lhs.checked_rem(rhs)?),
+        BinaryOp::Pow => {
+            match u64::try_from(non_negative(rhs, "exponent")?) {
+                Ok(exp) => Ok(lhs.checked_pow(exp, MAX_BITS)?),
+                // only 0, 1 and -1 have powers this large that fit
+                Err(_) if lhs.bit_length() > 1 => Err(CalcError::TooLarge),
+                Err(_) if lhs.sign == Sign::Minus && !rhs.bit(0) => Ok(BigInt::from(1)),
+                Err(_) => Ok(lhs.clone()),
+            }
+        }
+        BinaryOp::Shl => {
+            let shift: u32 = small(rhs, "shift", MAX_BITS)?;
+            check_size(lhs << shift)
+        }
+        BinaryOp::Shr => {
+            // shifting by more than the length gives 0 or -1, so huge shifts can be clamped
+            let shift = u32::try_from(non_negative(rhs, "shift")?).unwrap_or(u32::MAX);
+            Ok(lhs >> shift)
+        }
+    }
+}
+
+fn call(name: &str, args: &[BigInt]) -> Result<BigInt, CalcError> {
+    let Some(&(_, params, _)) = FUNCTIONS.iter().find(|(f, _, _)| *f == name) else {
+        return Err(CalcError::UnknownFunction(name.to_string()));
+    };
+    let expected = params.split(',').count();
+    if args.len() != expected {
+        return Err(CalcError::WrongArity {
+            name: name.to_string(),
+            expected,
+            found: args.len(),
+        });
+    }
+
+    let value = match (name, args) {
+        ("abs", [x]) => BigInt::from(x.inner.clone()),
+        ("gcd", [a, b]) => a.gcd(b),
+        ("lcm", [a, b]) => a.lcm(b),
+        ("sqrt", [x]) => BigInt::from(non_negative(x, "argument of sqrt")?.sqrt()),
+        ("root", [x, n]) => {
+            let n: u32 = small(n, "degree", u64::from(u32::MAX))?;
+            if n == 0 {
+                return Err(CalcError::Domain("degree must be positive".to_string()));
+            }
+            let x = non_negative(x, "argument of root")?;
+            BigInt::from(x.nth_root(n))
+        }
+        ("modpow", [b, e, m]) => {
+            let m = non_negative(m, "modulus")?;
+            if m.is_zero() {
+                return Err(CalcError::DivisionByZero);
+            }
+            let b = b.rem_euclid(&BigInt::from(m.clone()));
+            let e = non_negative(e, "exponent")?;
+            BigInt::from(b.inner.modpow(e, m))
+        }
+        ("modinv", [a, m]) => {
+            if m.sign == Sign::Minus || m.inner.is_zero() {
+                return Err(CalcError::Domain("modulus must be positive".to_string()));
+            }
+            a.modinv(m)
+                .ok_or_else(|| CalcError::Domain(format!("{a} has no inverse modulo {m}")))?
+        }
+        ("factorial", [n]) => BigInt::from(combinatorics::factorial(small(n, "n", 10_000_000)?)),
+        ("binomial", [n, k]) => {
+            let n: u64 = small(n, "n", u64::MAX)?;
+            let k: u64 = small(k, "k", u64::MAX)?;
+            // C(n, k) has less than min(k, n - k) * log2(n) bits
+            if k.min(n.saturating_sub(k)).saturating_mul(64) > MAX_BITS {
+                return Err(CalcError::TooLarge);
+            }
+            BigInt::from(combinatorics::binomial(n, k))
+        }
+        // F(n) has about 0.694n bits
+        ("fib", [n]) => BigInt::from(combinatorics::fibonacci(small(n, "n", MAX_BITS)?)),
+        ("isprime", [n]) => {
+            let prime = n.sign == Sign::Plus && n.inner.is_probable_prime(0);
+            BigInt::from(i32::from(prime))
+        }
+        ("nextprime", [n]) => match n.sign {
+            Sign::Plus => BigInt::from(n.inner.next_prime()),
+            Sign::Minus => BigInt::from(2),
+        },
+        ("bits", [x]) => BigInt::from(x.bit_length()),
+        ("digits", [x]) => BigInt::from(x.inner.to_string().len() as u64),
+        _ => unreachable!("arity is checked above"),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(line: &str) -> Result<String, CalcError> {
+        Ok(Calculator::new().execute(line)?.unwrap().to_string())
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        for (line, expected) in [
+            ("1 + 2 * 3", "7"),
+            ("(1 + 2) * 3", "9"),
+            ("-7 / 2", "-3"),
+            ("-7 % 2", "-1"),
+            ("2^100", "1267650600228229401496703205376"),
+            ("-2^2", "-4"),
+            ("(-2)^3", "-8"),
+            ("0^0", "1"),
+            ("1 << 64", "18446744073709551616"),
+            ("0x100 >> 4", "16"),
+            ("-5 >> 1", "-3"),
+            ("-1 >> 100", "-1"),
+            ("1 >> 0x1_0000_0000_0000", "0"),
+            ("0b1111 - 0o17", "0"),
+            ("1^(10^30)", "1"),
+            ("(-1)^(10^30 + 1)", "-1"),
+        ] {
+            assert_eq!(run(line).unwrap(), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn function_test() {
+        for (line, expected) in [
+            ("abs(-5)", "5"),
+            ("gcd(-12, 18)", "6"),
+            ("lcm(4, 6)", "12"),
+            ("sqrt(10^40 + 1)", "100000000000000000000"),
+            ("root(2^90, 3)", "1073741824"),
+            ("modpow(2, 10, 1000)", "24"),
+            ("modpow(-2, 3, 5)", "2"),
+            ("modinv(3, 7)", "5"),
+            ("factorial(20)", "2432902008176640000"),
+            ("binomial(10, 3)", "120"),
+            ("fib(100)", "354224848179261915075"),
+            ("isprime(2^61 - 1)", "1"),
+            ("isprime(-7)", "0"),
+            ("nextprime(100)", "101"),
+            ("bits(-256)", "9"),
+            ("digits(0)", "1"),
+            ("digits(-12345)", "5"),
+        ] {
+            assert_eq!(run(line).unwrap(), expected, "{line}");
+        }
+    }
+
+    #[test]
+    fn variable_test() {
+        let mut calc = Calculator::new();
+        assert_eq!(
+            calc.execute("x = 2^64; y = x - 1").unwrap(),
+            Some(BigInt::from(u64::MAX))
+        );
+        assert_eq!(
+            calc.execute("x * y").unwrap(),
+            Some(BigInt::from(u128::MAX - u128::from(u64::MAX)))
+        );
+        assert_eq!(calc.execute("ans % 10").unwrap(), Some(BigInt::from(0)));
+        assert_eq!(calc.execute("").unwrap(), None);
+        assert_eq!(
+            calc.execute("z + 1"),
+            Err(CalcError::UnknownVariable("z".to_string()))
+        );
+        // a failed line doesn't change the variables
+        assert!(calc.execute("x = 5; w = 1; 1 / 0").is_err());
+        assert_eq!(calc.execute("ans").unwrap(), Some(BigInt::from(0)));
+        assert_eq!(calc.execute("x").unwrap(), Some(BigInt::from(1u128 << 64)));
+        assert!(calc.execute("w").is_err());
+    }
+
+    #[test]
+    fn error_test() {
+        for (line, expected) in [
+            ("1 / 0", CalcError::DivisionByZero),
+            ("5 % (3 - 3)", CalcError::DivisionByZero),
+            ("modpow(2, 3, 0)", CalcError::DivisionByZero),
+            ("10^10^10", CalcError::TooLarge),
+            ("1 << 2^40", CalcError::TooLarge),
+            ("factorial(10^9)", CalcError::TooLarge),
+            ("2^(10^30)", CalcError::TooLarge),
+            ("foo(1)", CalcError::UnknownFunction("foo".to_string())),
+            (
+                "gcd(1)",
+                CalcError::WrongArity {
+                    name: "gcd".to_string(),
+                    expected: 2,
+                    found: 1,
+                },
+            ),
+        ] {
+            assert_eq!(run(line), Err(expected), "{line}");
+        }
+
+        for line in ["2^-1", "sqrt(-4)", "1 << -1", "modinv(2, 4)", "root(8, 0)"] {
+            assert!(matches!(run(line), Err(CalcError::Domain(_))), "{line}");
+        }
+        // the deepest expressions the parser accepts evaluate without overflowing the stack
+        let depth = parser::MAX_DEPTH - 1;
+        assert_eq!(
+            run(&format!("1{}", "+1".repeat(depth))).unwrap(),
+            parser::MAX_DEPTH.to_string()
+        );
+        assert_eq!(
+            run(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))).unwrap(),
+            "1"
+        );
+        assert!(matches!(
+            run(&"-".repeat(100_000)),
+            Err(CalcError::Parse(_))
+        ));
+        assert_eq!(
+            run("2 +").unwrap_err().to_string(),
+            "expected a number, a name or '(', found end of input at position 3"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+// Calculator over arbitrary precision integers.
+// `bigcalc` starts an interactive session, `bigcalc 'expression'` prints one result.
+
+mod eval;
+mod parser;
+
+use std::io::{BufRead, IsTerminal, Write};
+
+use eval::{Calculator, FUNCTIONS};
+
+const USAGE: &str = "\
+Usage: bigcalc [expression]
+
+Evaluates integer expressions. Without an expression, reads them line by line.
+
+Operators, from the lowest precedence:  << >>   + -   * / %   unary -   ^
+Division truncates towards zero and >> rounds towards negative infinity.
+Literals may be decimal, 0x hexadecimal, 0o octal or 0b binary, with optional
+'_' separators. `x = expr` assigns a variable, ';' separates statements and
+`ans` holds the last result. A line with an error assigns nothing.";
+
+fn print_help() {
+    println!("{USAGE}\n\nFunctions:");
+    for (name, params, description) in FUNCTIONS {
+        println!("  {:<20}{description}", format!("{name}({params})"));
+    }
+}
+
+fn repl() {
+    let mut calc = Calculator::new();
+    let interactive = std::io::stdin().is_terminal();
+    let mut lines = std::io::stdin().lock().lines();
+
+    loop {
+        if interactive {
+            print!("> ");
+            std::io::stdout()
+                .flush()
+                .expect("Failed to write to stdout");
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line.expect("Failed to read from stdin");
+
+        match line.trim() {
+            "exit" | "quit" => break,
+            "help" => print_help(),
+            line => match calc.execute(line) {
+                Ok(Some(value)) => println!("{value}"),
+                Ok(None) => {}
+                Err(error) => eprintln!("error: {error}"),
+            },
+        }
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match args.iter().map(|s| &**s).collect::<Vec<_>>()[..] {
+        [] => repl(),
+        ["-h" | "--help"] => print_help(),
+        _ => match Calculator::new().execute(&args.join(" ")) {
+            Ok(Some(value)) => println!("{value}"),
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("error: {error}");
+                std::process::exit(1);
+            }
+        },
+    }
+}
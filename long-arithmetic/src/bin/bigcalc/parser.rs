@@ -0,0 +1,472 @@
+use std::fmt::Display;
+
+use long_arithmetic::bigint::BigInt;
+use long_arithmetic::biguint::BigUInt;
+
+// Grammar, from the lowest precedence:
+//   line      = statement (';' statement)*
+//   statement = identifier '=' shift | shift
+//   shift     = sum (('<<' | '>>') sum)*
+//   sum       = product (('+' | '-') product)*
+//   product   = unary (('*' | '/' | '%') unary)*
+//   unary     = ('-' | '+') unary | power
+//   power     = atom ('^' unary)?
+//   atom      = number | identifier | identifier '(' arguments ')' | '(' shift ')'
+// so `-2^2` is -4 and `2^3^2` is 2^9, as in mathematics
+
+// limit on the depth of the expression tree, so that parsing and evaluation, which are
+// both recursive, can't overflow the stack. Debug builds use several KiB of stack per level
+pub const MAX_DEPTH: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // byte offset in the parsed line
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Shl,
+    Shr,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Number(BigInt),
+    Variable(String),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    Assign(String, Expr),
+    Expr(Expr),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Number(BigUInt),
+    Identifier(String),
+    Op(BinaryOp),
+    LeftParen,
+    RightParen,
+    Comma,
+    Assign,
+    Semicolon,
+    End,
+}
+
+fn tokenize(line: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let bytes = line.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let token = match bytes[i] {
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                i += 1;
+                continue;
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                Token::Number(parse_number(&line[start..i], start)?)
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                Token::Identifier(line[start..i].to_string())
+            }
+            _ => {
+                let (token, len) = match &bytes[i..] {
+                    [b'<', b'<', ..] => (Token::Op(BinaryOp::Shl), 2),
+                    [b'>', b'>', ..] => (Token::Op(BinaryOp::Shr), 2),
+                    [b'+', ..] => (Token::Op(BinaryOp::Add), 1),
+                    [b'-', ..] => (Token::Op(BinaryOp::Sub), 1),
+                    [b'*', ..] => (Token::Op(BinaryOp::Mul), 1),
+                    [b'/', ..] => (Token::Op(BinaryOp::Div), 1),
+                    [b'%', ..] => (Token::Op(BinaryOp::Rem), 1),
+                    [b'^', ..] => (Token::Op(BinaryOp::Pow), 1),
+                    [b'(', ..] => (Token::LeftParen, 1),
+                    [b')', ..] => (Token::RightParen, 1),
+                    [b',', ..] => (Token::Comma, 1),
+                    [b'=', ..] => (Token::Assign, 1),
+                    [b';', ..] => (Token::Semicolon, 1),
+                    _ => {
+                        let ch = line[i..].chars().next().unwrap();
+                        return Err(ParseError {
+                            position: i,
+                            message: format!("unexpected character '{ch}'"),
+                        });
+                    }
+                };
+                i += len;
+                token
+            }
+        };
+        tokens.push((start, token));
+    }
+
+    tokens.push((line.len(), Token::End));
+    Ok(tokens)
+}
+
+// decimal, `0x` hexadecimal, `0o` octal or `0b` binary digits, `_` separates groups
+fn parse_number(literal: &str, position: usize) -> Result<BigUInt, ParseError> {
+    let (digits, radix, offset) = match literal.get(..2) {
+        Some("0x" | "0X") => (&literal[2..], 16, 2),
+        Some("0o" | "0O") => (&literal[2..], 8, 2),
+        Some("0b" | "0B") => (&literal[2..], 2, 2),
+        _ => (literal, 10, 0),
+    };
+
+    let invalid = |i: usize| ParseError {
+        position: position + offset + i,
+        message: format!("invalid number '{literal}'"),
+    };
+    let cleaned: String = digits.chars().filter(|&ch| ch != '_').collect();
+    BigUInt::from_str_radix(&cleaned, radix).map_err(|_| {
+        let bad = digits
+            .char_indices()
+            .find(|&(_, ch)| ch != '_' && !ch.is_digit(radix))
+            .map_or(0, |(i, _)| i);
+        invalid(bad)
+    })
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // depth of the expression being parsed
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].1
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.next].0
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.next].1.clone();
+        if token != Token::End {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn error<T>(&self, expected: &str) -> Result<T, ParseError> {
+        let found = match self.peek() {
+            Token::End => "end of input".to_string(),
+            Token::Number(n) => format!("number {n}"),
+            Token::Identifier(name) => format!("'{name}'"),
+            _ => "operator".to_string(),
+        };
+        Err(ParseError {
+            position: self.position(),
+            message: format!("expected {expected}, found {found}"),
+        })
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<(), ParseError> {
+        if *self.peek() == token {
+            self.advance();
+            Ok(())
+        } else {
+            self.error(expected)
+        }
+    }
+
+    fn nest(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError {
+                position: self.position(),
+                message: "expression nested too deeply".to_string(),
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn statement(&mut self) -> Result<Statement, ParseError> {
+        if let (Token::Identifier(name), Some((_, Token::Assign))) =
+            (self.peek(), self.tokens.get(self.next + 1))
+        {
+            let name = name.clone();
+            self.next += 2;
+            return Ok(Statement::Assign(name, self.shift()?));
+        }
+        Ok(Statement::Expr(self.shift()?))
+    }
+
+    // left-associative chain of the operators in `ops` over `operand`
+    fn chain(
+        &mut self,
+        ops: &[BinaryOp],
+        operand: fn(&mut Self) -> Result<Expr, ParseError>,
+    ) -> Result<Expr, ParseError> {
+        let depth = self.depth;
+        let mut lhs = operand(self)?;
+        while let Token::Op(op) = *self.peek() {
+            if !ops.contains(&op) {
+                break;
+            }
+            // the tree leans to the left, so every operator adds a level to it
+            self.nest()?;
+            self.advance();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(operand(self)?));
+        }
+        self.depth = depth;
+        Ok(lhs)
+    }
+
+    fn shift(&mut self) -> Result<Expr, ParseError> {
+        self.chain(&[BinaryOp::Shl, BinaryOp::Shr], Self::sum)
+    }
+
+    fn sum(&mut self) -> Result<Expr, ParseError> {
+        self.chain(&[BinaryOp::Add, BinaryOp::Sub], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Expr, ParseError> {
+        self.chain(&[BinaryOp::Mul, BinaryOp::Div, BinaryOp::Rem], Self::unary)
+    }
+
+    // parentheses, arguments, signs and exponents all nest through here
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        let depth = self.depth;
+        self.nest()?;
+        let expr = match self.peek() {
+            Token::Op(BinaryOp::Sub) => {
+                self.advance();
+                Expr::Neg(Box::new(self.unary()?))
+            }
+            Token::Op(BinaryOp::Add) => {
+                self.advance();
+                self.unary()?
+            }
+            _ => self.power()?,
+        };
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let base = self.atom()?;
+        if *self.peek() == Token::Op(BinaryOp::Pow) {
+            self.advance();
+            let exp = self.unary()?;
+            return Ok(Expr::Binary(BinaryOp::Pow, Box::new(base), Box::new(exp)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().clone() {
+            Token::Number(n) => {
+                self.advance();
+                Ok(Expr::Number(BigInt::from(n)))
+            }
+            Token::Identifier(name) => {
+                self.advance();
+                if *self.peek() != Token::LeftParen {
+                    return Ok(Expr::Variable(name));
+                }
+                self.advance();
+
+                let mut args = vec![];
+                if *self.peek() != Token::RightParen {
+                    args.push(self.shift()?);
+                    while *self.peek() == Token::Comma {
+                        self.advance();
+                        args.push(self.shift()?);
+                    }
+                }
+                self.expect(Token::RightParen, "',' or ')'")?;
+                Ok(Expr::Call(name, args))
+            }
+            Token::LeftParen => {
+                self.advance();
+                let expr = self.shift()?;
+                self.expect(Token::RightParen, "')'")?;
+                Ok(expr)
+            }
+            _ => self.error("a number, a name or '('"),
+        }
+    }
+}
+
+// statements of a line separated by ';', empty statements are skipped
+pub fn parse(line: &str) -> Result<Vec<Statement>, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(line)?,
+        next: 0,
+        depth: 0,
+    };
+
+    let mut statements = vec![];
+    loop {
+        match parser.peek() {
+            Token::End => return Ok(statements),
+            Token::Semicolon => {
+                parser.advance();
+            }
+            _ => {
+                statements.push(parser.statement()?);
+                if !matches!(parser.peek(), Token::End | Token::Semicolon) {
+                    return parser.error("an operator or ';'");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num(n: i64) -> Box<Expr> {
+        Box::new(Expr::Number(BigInt::from(n)))
+    }
+
+    fn expr(line: &str) -> Expr {
+        match parse(line).unwrap().as_slice() {
+            [Statement::Expr(expr)] => expr.clone(),
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn number_test() {
+        assert_eq!(expr("0x_ff"), *num(255));
+        assert_eq!(expr("0b1010_1010"), *num(170));
+        assert_eq!(expr("0o17"), *num(15));
+        assert_eq!(expr("1_000_000"), *num(1_000_000));
+        assert_eq!(
+            expr("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"),
+            Expr::Number(BigInt::from(u128::MAX))
+        );
+
+        let error = parse("1 + 0b102").unwrap_err();
+        assert_eq!(error.position, 8);
+        assert!(error.message.contains("0b102"), "{error}");
+        assert_eq!(parse("0x").unwrap_err().position, 2);
+    }
+
+    #[test]
+    fn precedence_test() {
+        use BinaryOp::*;
+
+        // 1 + 2 * 3 << 1
+        assert_eq!(
+            expr("1 + 2 * 3 << 1"),
+            Expr::Binary(
+                Shl,
+                Box::new(Expr::Binary(
+                    Add,
+                    num(1),
+                    Box::new(Expr::Binary(Mul, num(2), num(3)))
+                )),
+                num(1)
+            )
+        );
+        // -(2^(3^2))
+        assert_eq!(
+            expr("-2^3^2"),
+            Expr::Neg(Box::new(Expr::Binary(
+                Pow,
+                num(2),
+                Box::new(Expr::Binary(Pow, num(3), num(2)))
+            )))
+        );
+        // (10 - 4) - 3
+        assert_eq!(
+            expr("10 - 4 - 3"),
+            Expr::Binary(Sub, Box::new(Expr::Binary(Sub, num(10), num(4))), num(3))
+        );
+        assert_eq!(
+            expr("2^-1"),
+            Expr::Binary(Pow, num(2), Box::new(Expr::Neg(num(1))))
+        );
+        assert_eq!(expr("((+7))"), *num(7));
+    }
+
+    #[test]
+    fn statement_test() {
+        assert_eq!(
+            parse("x = gcd(a, 12); ; x").unwrap(),
+            vec![
+                Statement::Assign(
+                    "x".to_string(),
+                    Expr::Call(
+                        "gcd".to_string(),
+                        vec![Expr::Variable("a".to_string()), *num(12)]
+                    )
+                ),
+                Statement::Expr(Expr::Variable("x".to_string())),
+            ]
+        );
+        assert_eq!(parse("  ").unwrap(), vec![]);
+        assert_eq!(expr("f()"), Expr::Call("f".to_string(), vec![]));
+    }
+
+    #[test]
+    fn error_test() {
+        for (line, position) in [
+            ("1 +", 3),
+            ("(1 + 2", 6),
+            ("1 2", 2),
+            ("gcd(1 2)", 6),
+            ("3 = 4", 2),
+            ("2 $ 3", 2),
+            ("*", 0),
+        ] {
+            let error = parse(line).unwrap_err();
+            assert_eq!(error.position, position, "{line}: {error}");
+        }
+        for line in [
+            format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000)),
+            format!("{}1", "-".repeat(100_000)),
+            format!("2{}", "^2".repeat(100_000)),
+            format!("1{}", "+1".repeat(100_000)),
+            format!("f({}1{})", "f(".repeat(100_000), ")".repeat(100_000)),
+        ] {
+            let error = parse(&line).unwrap_err();
+            assert_eq!(error.message, "expression nested too deeply");
+        }
+        // the outermost operand takes a level too
+        let parens = |n| format!("{}1{}", "(".repeat(n), ")".repeat(n));
+        assert!(parse(&parens(MAX_DEPTH - 1)).is_ok());
+        assert!(parse(&parens(MAX_DEPTH)).is_err());
+        assert!(parse(&format!("1{}", "+1".repeat(MAX_DEPTH - 1))).is_ok());
+        assert!(parse(&format!("1{}", "+1".repeat(MAX_DEPTH))).is_err());
+
+        assert_eq!(
+            parse("(1").unwrap_err().to_string(),
+            "expected ')', found end of input at position 2"
+        );
+    }
+}
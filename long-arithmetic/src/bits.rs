@@ -0,0 +1,326 @@
+use super::{BigInt, BigUInt, Sign};
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Not};
+
+// `BigUInt` has no `Not`: complement of a non-negative number with infinitely
+// many leading zeros is negative, use `BigInt` for that
+
+fn trim(value: &mut BigUInt) {
+    while value.0.last() == Some(&0) {
+        let _ = value.0.pop();
+    }
+}
+
+impl BitAndAssign<&BigUInt> for BigUInt {
+    fn bitand_assign(&mut self, rhs: &BigUInt) {
+        self.0.truncate(rhs.0.len());
+        for (a, b) in self.0.iter_mut().zip(&rhs.0) {
+            *a &= b;
+        }
+        trim(self);
+    }
+}
+
+impl BitOrAssign<&BigUInt> for BigUInt {
+    fn bitor_assign(&mut self, rhs: &BigUInt) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), 0);
+        }
+        for (a, b) in self.0.iter_mut().zip(&rhs.0) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitXorAssign<&BigUInt> for BigUInt {
+    fn bitxor_assign(&mut self, rhs: &BigUInt) {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), 0);
+        }
+        for (a, b) in self.0.iter_mut().zip(&rhs.0) {
+            *a ^= b;
+        }
+        trim(self);
+    }
+}
+
+impl BigUInt {
+    #[must_use]
+    pub fn bit(&self, n: u64) -> bool {
+        let (limb, bit) = ((n / 64) as usize, n % 64);
+        self.0.get(limb).is_some_and(|limb| (limb >> bit) & 1 == 1)
+    }
+
+    pub fn set_bit(&mut self, n: u64) {
+        let (limb, bit) = ((n / 64) as usize, n % 64);
+        if self.0.len() <= limb {
+            self.0.resize(limb + 1, 0);
+        }
+        self.0[limb] |= 1 << bit;
+    }
+
+    pub fn clear_bit(&mut self, n: u64) {
+        let (limb, bit) = ((n / 64) as usize, n % 64);
+        if let Some(limb) = self.0.get_mut(limb) {
+            *limb &= !(1 << bit);
+            trim(self);
+        }
+    }
+
+    // number of bits without leading zeros, 0 for zero
+    #[must_use]
+    pub fn bit_length(&self) -> u64 {
+        u64::from(self.bits_num())
+    }
+
+    #[must_use]
+    pub fn count_ones(&self) -> u64 {
+        self.0.iter().map(|limb| u64::from(limb.count_ones())).sum()
+    }
+
+    // None for zero, which has infinitely many trailing zeros
+    #[must_use]
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        let zero_limbs = self.0.iter().take_while(|&&limb| limb == 0).count();
+        let limb = self.0.get(zero_limbs)?;
+        Some(zero_limbs as u64 * 64 + u64::from(limb.trailing_zeros()))
+    }
+}
+
+// `len` limbs of the two's complement representation, the rest are sign bits.
+// -x = !(x - 1), so the limbs of a negative number are inverted after subtracting one
+pub(crate) fn to_twos_complement(value: &BigInt, len: usize) -> Vec<u64> {
+    let mut limbs = value.inner.0.to_vec();
+    limbs.resize(len, 0);
+    if value.sign == Sign::Minus {
+        let mut borrow = true;
+        for limb in &mut limbs {
+            (*limb, borrow) = limb.overflowing_sub(u64::from(borrow));
+            *limb = !*limb;
+        }
+    }
+    limbs
+}
+
+pub(crate) fn from_twos_complement(mut limbs: Vec<u64>, negative: bool) -> BigInt {
+    if negative {
+        // x = !(-x) + 1
+        let mut carry = true;
+        for limb in &mut limbs {
+            (*limb, carry) = (!*limb).overflowing_add(u64::from(carry));
+        }
+    }
+    let mut inner = BigUInt::from(limbs);
+    trim(&mut inner);
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    BigInt::from((inner, sign)).fix_zero()
+}
+
+// applies `op` to every bit of infinite two's complement representations
+fn bitwise(lhs: &BigInt, rhs: &BigInt, op: impl Fn(u64, u64) -> u64) -> BigInt {
+    // one more limb so that the magnitude of a negative result always fits
+    let len = lhs.inner.0.len().max(rhs.inner.0.len()) + 1;
+    let sign_limb = |value: &BigInt| match value.sign {
+        Sign::Plus => 0,
+        Sign::Minus => u64::MAX,
+    };
+
+    let limbs = to_twos_complement(lhs, len)
+        .into_iter()
+        .zip(to_twos_complement(rhs, len))
+        .map(|(a, b)| op(a, b))
+        .collect();
+    from_twos_complement(limbs, op(sign_limb(lhs), sign_limb(rhs)) != 0)
+}
+
+impl BitAndAssign<&BigInt> for BigInt {
+    fn bitand_assign(&mut self, rhs: &BigInt) {
+        if self.sign == Sign::Plus && rhs.sign == Sign::Plus {
+            self.inner &= &rhs.inner;
+        } else {
+            *self = bitwise(self, rhs, |a, b| a & b);
+        }
+    }
+}
+
+impl BitOrAssign<&BigInt> for BigInt {
+    fn bitor_assign(&mut self, rhs: &BigInt) {
+        if self.sign == Sign::Plus && rhs.sign == Sign::Plus {
+            self.inner |= &rhs.inner;
+        } else {
+            *self = bitwise(self, rhs, |a, b| a | b);
+        }
+    }
+}
+
+impl BitXorAssign<&BigInt> for BigInt {
+    fn bitxor_assign(&mut self, rhs: &BigInt) {
+        if self.sign == Sign::Plus && rhs.sign == Sign::Plus {
+            self.inner ^= &rhs.inner;
+        } else {
+            *self = bitwise(self, rhs, |a, b| a ^ b);
+        }
+    }
+}
+
+// !x = -x - 1
+impl Not for BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        match self.sign {
+            Sign::Plus => BigInt::from((self.inner + 1, Sign::Minus)),
+            Sign::Minus => BigInt::from(self.inner - 1),
+        }
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        !self.clone()
+    }
+}
+
+impl BigInt {
+    // bit of the two's complement representation, negative numbers have infinitely many ones
+    #[must_use]
+    pub fn bit(&self, n: u64) -> bool {
+        match self.sign {
+            Sign::Plus => self.inner.bit(n),
+            // bit n of !(x - 1)
+            Sign::Minus => !(self.inner.clone() - 1).bit(n),
+        }
+    }
+
+    pub fn set_bit(&mut self, n: u64) {
+        if self.sign == Sign::Plus {
+            self.inner.set_bit(n);
+        } else if !self.bit(n) {
+            let mut bit = BigInt::from(0);
+            bit.inner.set_bit(n);
+            *self |= &bit;
+        }
+    }
+
+    pub fn clear_bit(&mut self, n: u64) {
+        if self.sign == Sign::Plus {
+            self.inner.clear_bit(n);
+        } else if self.bit(n) {
+            let mut bit = BigInt::from(0);
+            bit.inner.set_bit(n);
+            *self ^= &bit;
+        }
+    }
+
+    // like Python's `int.bit_length`, the length of the magnitude
+    #[must_use]
+    pub fn bit_length(&self) -> u64 {
+        self.inner.bit_length()
+    }
+
+    // like Python's `int.bit_count`, ones of the magnitude
+    #[must_use]
+    pub fn count_ones(&self) -> u64 {
+        self.inner.count_ones()
+    }
+
+    // same for the magnitude and the two's complement representation
+    #[must_use]
+    pub fn trailing_zeros(&self) -> Option<u64> {
+        self.inner.trailing_zeros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(value: i128) -> BigInt {
+        value.to_string().parse().unwrap()
+    }
+
+    #[test]
+    fn biguint_ops_test() {
+        let a = BigUInt::from(vec![0xF0F0, u64::MAX, 1]);
+        let b = BigUInt::from(vec![0xFF00, u64::MAX]);
+        assert_eq!(&a & &b, BigUInt::from(vec![0xF000, u64::MAX]));
+        assert_eq!(&a | &b, BigUInt::from(vec![0xFFF0, u64::MAX, 1]));
+        assert_eq!(&a ^ &b, BigUInt::from(vec![0x0FF0, 0, 1]));
+        // leading zero limbs are removed
+        assert_eq!(&b ^ &b, BigUInt::from(0u64));
+        assert_eq!(&a & &BigUInt::from(vec![0, 0, 2]), BigUInt::from(0u64));
+
+        let mut c = a.clone();
+        c |= b.clone();
+        c ^= &a;
+        assert_eq!(c, BigUInt::from(0x0F00u64));
+    }
+
+    #[test]
+    fn bigint_ops_test() {
+        let values = [
+            0i128,
+            1,
+            -1,
+            5,
+            -6,
+            u64::MAX as i128,
+            -(u64::MAX as i128),
+            -(1 << 64),
+            (1 << 64) + 12_345,
+            i128::MAX,
+            i128::MIN + 1,
+            -(3 << 125),
+        ];
+        for a in values {
+            assert_eq!(!big(a), big(!a), "!{a}");
+            for b in values {
+                let (x, y) = (big(a), big(b));
+                assert_eq!(&x & &y, big(a & b), "{a} & {b}");
+                assert_eq!(&x | &y, big(a | b), "{a} | {b}");
+                assert_eq!(&x ^ &y, big(a ^ b), "{a} ^ {b}");
+            }
+        }
+
+        // both operands are negative and the result needs an extra limb
+        let a = -(BigInt::from(1) << 127);
+        let b = -(BigInt::from(3) << 126);
+        assert_eq!(a & b, -(BigInt::from(1) << 128));
+    }
+
+    #[test]
+    fn bit_api_test() {
+        let mut a = BigUInt::from(0u64);
+        a.set_bit(200);
+        a.set_bit(3);
+        assert!(a.bit(200) && a.bit(3) && !a.bit(4) && !a.bit(1000));
+        assert_eq!(a.bit_length(), 201);
+        assert_eq!(a.count_ones(), 2);
+        assert_eq!(a.trailing_zeros(), Some(3));
+        a.clear_bit(200);
+        assert_eq!(a, BigUInt::from(8u64));
+        a.clear_bit(1000);
+        assert_eq!(a, BigUInt::from(8u64));
+        assert_eq!(BigUInt::from(0u64).trailing_zeros(), None);
+        assert_eq!(BigUInt::from(0u64).bit_length(), 0);
+
+        for value in [0i128, 12, -12, -1, -(1 << 70), (1 << 70) + 1] {
+            for n in [0, 1, 2, 3, 64, 70, 71, 100] {
+                let x = big(value);
+                assert_eq!(x.bit(n), (value >> n) & 1 == 1, "bit {n} of {value}");
+
+                let mut set = x.clone();
+                set.set_bit(n);
+                assert_eq!(set, big(value | 1 << n), "set bit {n} of {value}");
+
+                let mut clear = x.clone();
+                clear.clear_bit(n);
+                assert_eq!(clear, big(value & !(1 << n)), "clear bit {n} of {value}");
+            }
+        }
+        assert_eq!(big(-12).trailing_zeros(), Some(2));
+        assert_eq!(big(-12).bit_length(), 4);
+        assert_eq!(big(-12).count_ones(), 2);
+    }
+}
@@ -1,198 +1,546 @@
-use super::{BigInt, BigUInt};
-use replace_with::replace_with;
+use super::{BigInt, BigUInt, Sign};
+use crate::decimal::BigDecimal;
+use crate::rational::BigRational;
+use std::iter::{Product, Sum};
 use std::ops::{
-    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, ShlAssign, ShrAssign, Sub,
-    SubAssign,
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
 };
 
-impl AddAssign for BigUInt {
-    fn add_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ + rhs);
+// Every binary operator is implemented for all four combinations of owned and
+// borrowed operands. Operations that can work in place (`+`, `-`, bitwise, shifts, and
+// `*`, `/`, `%` of the integer types) are implemented through `op=` with a borrowed
+// right-hand side and reuse the allocation of an owned operand; the others are
+// implemented for `&T op &T`.
+
+// `+`: commutative, so `&T + T` reuses the right-hand allocation
+macro_rules! forward_add {
+    ($t:ty) => {
+        impl Add<$t> for $t {
+            type Output = $t;
+            fn add(mut self, rhs: $t) -> $t {
+                self += &rhs;
+                self
+            }
+        }
+
+        impl Add<&$t> for $t {
+            type Output = $t;
+            fn add(mut self, rhs: &$t) -> $t {
+                self += rhs;
+                self
+            }
+        }
+
+        impl Add<$t> for &$t {
+            type Output = $t;
+            fn add(self, mut rhs: $t) -> $t {
+                rhs += self;
+                rhs
+            }
+        }
+
+        impl Add<&$t> for &$t {
+            type Output = $t;
+            fn add(self, rhs: &$t) -> $t {
+                self.clone() + rhs
+            }
+        }
+
+        impl AddAssign<$t> for $t {
+            fn add_assign(&mut self, rhs: $t) {
+                *self += &rhs;
+            }
+        }
+    };
+}
+
+// `-`: `&T - T` is type specific and implemented separately
+macro_rules! forward_sub {
+    ($t:ty) => {
+        impl Sub<$t> for $t {
+            type Output = $t;
+            fn sub(mut self, rhs: $t) -> $t {
+                self -= &rhs;
+                self
+            }
+        }
+
+        impl Sub<&$t> for $t {
+            type Output = $t;
+            fn sub(mut self, rhs: &$t) -> $t {
+                self -= rhs;
+                self
+            }
+        }
+
+        impl Sub<&$t> for &$t {
+            type Output = $t;
+            fn sub(self, rhs: &$t) -> $t {
+                self.clone() - rhs
+            }
+        }
+
+        impl SubAssign<$t> for $t {
+            fn sub_assign(&mut self, rhs: $t) {
+                *self -= &rhs;
+            }
+        }
+    };
+}
+
+// `*`, `/` and `%` of the integer types: implemented for `&T op &T` and for `op=` with a
+// borrowed right-hand side separately, the assignment works in place
+macro_rules! forward_assign_binop {
+    ($t:ty, $imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident) => {
+        impl $imp<$t> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: $t) -> $t {
+                self.$method_assign(&rhs);
+                self
+            }
+        }
+
+        impl $imp<&$t> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: &$t) -> $t {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl $imp<$t> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: $t) -> $t {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $imp_assign<$t> for $t {
+            fn $method_assign(&mut self, rhs: $t) {
+                self.$method_assign(&rhs);
+            }
+        }
+    };
+}
+
+// operations producing a new value, implemented for `&T op &T`
+macro_rules! forward_ref_binop {
+    ($t:ty, $imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident) => {
+        impl $imp<$t> for $t {
+            type Output = $t;
+            fn $method(self, rhs: $t) -> $t {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $imp<&$t> for $t {
+            type Output = $t;
+            fn $method(self, rhs: &$t) -> $t {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $imp<$t> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: $t) -> $t {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $imp_assign<$t> for $t {
+            fn $method_assign(&mut self, rhs: $t) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+
+        impl $imp_assign<&$t> for $t {
+            fn $method_assign(&mut self, rhs: &$t) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    };
+}
+
+// bitwise operators: commutative like `+`, implemented through `op=`
+macro_rules! forward_bitop {
+    ($t:ty, $imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident) => {
+        impl $imp<$t> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: $t) -> $t {
+                self.$method_assign(&rhs);
+                self
+            }
+        }
+
+        impl $imp<&$t> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: &$t) -> $t {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl $imp<$t> for &$t {
+            type Output = $t;
+            fn $method(self, mut rhs: $t) -> $t {
+                rhs.$method_assign(self);
+                rhs
+            }
+        }
+
+        impl $imp<&$t> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: &$t) -> $t {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl $imp_assign<$t> for $t {
+            fn $method_assign(&mut self, rhs: $t) {
+                self.$method_assign(&rhs);
+            }
+        }
+    };
+}
+
+// shifts are implemented through `op=`
+macro_rules! forward_shift {
+    ($t:ty, $imp:ident, $method:ident, $method_assign:ident) => {
+        impl $imp<u32> for $t {
+            type Output = $t;
+            fn $method(mut self, rhs: u32) -> $t {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl $imp<u32> for &$t {
+            type Output = $t;
+            fn $method(self, rhs: u32) -> $t {
+                self.clone().$method(rhs)
+            }
+        }
+    };
+}
+
+// `Sum` and `Product` of owned and borrowed items, accumulated in place
+macro_rules! forward_fold {
+    ($t:ty) => {
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ZERO, |mut acc, item| {
+                    acc += item;
+                    acc
+                })
+            }
+        }
+
+        impl<'a> Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ZERO, |mut acc, item| {
+                    acc += item;
+                    acc
+                })
+            }
+        }
+
+        impl Product for $t {
+            fn product<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ONE, |mut acc, item| {
+                    acc *= item;
+                    acc
+                })
+            }
+        }
+
+        impl<'a> Product<&'a $t> for $t {
+            fn product<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ONE, |mut acc, item| {
+                    acc *= item;
+                    acc
+                })
+            }
+        }
+    };
+}
+
+forward_add!(BigUInt);
+forward_sub!(BigUInt);
+forward_assign_binop!(BigUInt, Mul, mul, MulAssign, mul_assign);
+forward_assign_binop!(BigUInt, Div, div, DivAssign, div_assign);
+forward_assign_binop!(BigUInt, Rem, rem, RemAssign, rem_assign);
+forward_bitop!(BigUInt, BitAnd, bitand, BitAndAssign, bitand_assign);
+forward_bitop!(BigUInt, BitOr, bitor, BitOrAssign, bitor_assign);
+forward_bitop!(BigUInt, BitXor, bitxor, BitXorAssign, bitxor_assign);
+forward_shift!(BigUInt, Shl, shl, shl_assign);
+forward_shift!(BigUInt, Shr, shr, shr_assign);
+
+forward_add!(BigInt);
+forward_sub!(BigInt);
+forward_assign_binop!(BigInt, Mul, mul, MulAssign, mul_assign);
+forward_assign_binop!(BigInt, Div, div, DivAssign, div_assign);
+forward_assign_binop!(BigInt, Rem, rem, RemAssign, rem_assign);
+forward_bitop!(BigInt, BitAnd, bitand, BitAndAssign, bitand_assign);
+forward_bitop!(BigInt, BitOr, bitor, BitOrAssign, bitor_assign);
+forward_bitop!(BigInt, BitXor, bitxor, BitXorAssign, bitxor_assign);
+forward_shift!(BigInt, Shl, shl, shl_assign);
+forward_shift!(BigInt, Shr, shr, shr_assign);
+
+forward_ref_binop!(BigRational, Add, add, AddAssign, add_assign);
+forward_ref_binop!(BigRational, Sub, sub, SubAssign, sub_assign);
+forward_ref_binop!(BigRational, Mul, mul, MulAssign, mul_assign);
+forward_ref_binop!(BigRational, Div, div, DivAssign, div_assign);
+
+forward_ref_binop!(BigDecimal, Add, add, AddAssign, add_assign);
+forward_ref_binop!(BigDecimal, Sub, sub, SubAssign, sub_assign);
+forward_ref_binop!(BigDecimal, Mul, mul, MulAssign, mul_assign);
+
+forward_fold!(BigUInt);
+forward_fold!(BigInt);
+forward_fold!(BigRational);
+forward_fold!(BigDecimal);
+
+impl Sub<BigUInt> for &BigUInt {
+    type Output = BigUInt;
+    fn sub(self, mut rhs: BigUInt) -> BigUInt {
+        rhs.sub_from(self);
+        rhs
+    }
+}
+
+impl Div<&BigUInt> for &BigUInt {
+    type Output = BigUInt;
+    fn div(self, rhs: &BigUInt) -> BigUInt {
+        let (div, _) = crate::div::div_rem(self, rhs);
+        div
+    }
+}
+
+impl Rem<&BigUInt> for &BigUInt {
+    type Output = BigUInt;
+    fn rem(self, rhs: &BigUInt) -> BigUInt {
+        let (_, rem) = crate::div::div_rem(self, rhs);
+        rem
+    }
+}
+
+impl MulAssign<&BigUInt> for BigUInt {
+    fn mul_assign(&mut self, rhs: &BigUInt) {
+        match (self.0.as_slice(), rhs.0.as_slice()) {
+            (_, [limb]) => *self *= *limb,
+            ([limb], _) => {
+                let limb = *limb;
+                self.0.clear();
+                self.0.extend_from_slice(&rhs.0);
+                *self *= limb;
+            }
+            // longer operands can't be overwritten while their product is computed
+            _ => *self = &*self * rhs,
+        }
+    }
+}
+
+impl DivAssign<&BigUInt> for BigUInt {
+    fn div_assign(&mut self, rhs: &BigUInt) {
+        let _ = crate::div::div_rem_in_place(self, rhs);
+    }
+}
+
+impl RemAssign<&BigUInt> for BigUInt {
+    fn rem_assign(&mut self, rhs: &BigUInt) {
+        let rem = match rhs.0.as_slice() {
+            [limb] => Self::from(crate::div::rem_limb(&self.0, *limb)),
+            _ => crate::div::div_rem_in_place(self, rhs),
+        };
+        // the remainder is copied into the buffer of the dividend
+        self.0.clear();
+        self.0.extend_from_slice(&rem.0);
     }
 }
 
 impl Add<u64> for BigUInt {
     type Output = Self;
-    fn add(self, rhs: u64) -> Self::Output {
-        self + Self::from(rhs)
+    fn add(mut self, rhs: u64) -> Self::Output {
+        self += rhs;
+        self
     }
 }
 
 impl AddAssign<u64> for BigUInt {
     fn add_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ + rhs);
-    }
-}
-
-impl SubAssign for BigUInt {
-    fn sub_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ - rhs);
+        crate::helpers::add_shifted(self, &[rhs], 0);
     }
 }
 
 impl Sub<u64> for BigUInt {
     type Output = Self;
-    fn sub(self, rhs: u64) -> Self::Output {
-        self - Self::from(rhs)
+    fn sub(mut self, rhs: u64) -> Self::Output {
+        self -= rhs;
+        self
     }
 }
 
 impl SubAssign<u64> for BigUInt {
     fn sub_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ - rhs);
-    }
-}
-
-impl MulAssign for BigUInt {
-    fn mul_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ * rhs);
+        *self -= &Self::from(rhs);
     }
 }
 
 impl Mul<u64> for BigUInt {
     type Output = Self;
-    fn mul(self, rhs: u64) -> Self::Output {
-        self * Self::from(rhs)
+    fn mul(mut self, rhs: u64) -> Self::Output {
+        self *= rhs;
+        self
     }
 }
 
 impl MulAssign<u64> for BigUInt {
     fn mul_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ * rhs);
-    }
-}
+        if rhs == 0 {
+            self.0.clear();
+            return;
+        }
 
-impl Div for BigUInt {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        let (div, _) = self.div_rem(rhs);
-        div
+        crate::helpers::mul_add_limb(&mut self.0, rhs, 0);
     }
 }
 
 impl Div<u64> for BigUInt {
     type Output = Self;
-    fn div(self, rhs: u64) -> Self::Output {
-        self / Self::from(rhs)
+    fn div(mut self, rhs: u64) -> Self::Output {
+        self /= rhs;
+        self
     }
 }
 
 impl DivAssign<u64> for BigUInt {
     fn div_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ / rhs);
+        let _ = crate::div::div_rem_limb_in_place(&mut self.0, rhs);
     }
 }
 
-impl DivAssign for BigUInt {
-    fn div_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ / rhs);
+impl Rem<u64> for BigUInt {
+    type Output = Self;
+    fn rem(mut self, rhs: u64) -> Self::Output {
+        self %= rhs;
+        self
     }
 }
 
-impl Rem for BigUInt {
-    type Output = Self;
-    fn rem(self, rhs: Self) -> Self::Output {
-        let (_, rem) = self.div_rem(rhs);
-        rem
+impl RemAssign<u64> for BigUInt {
+    fn rem_assign(&mut self, rhs: u64) {
+        let rem = crate::div::rem_limb(&self.0, rhs);
+        self.0.clear();
+        if rem != 0 {
+            self.0.push(rem);
+        }
     }
 }
 
-impl RemAssign for BigUInt {
-    fn rem_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ % rhs);
+// -----------------------------------------------------------------------------
+
+impl Sub<BigInt> for &BigInt {
+    type Output = BigInt;
+    fn sub(self, mut rhs: BigInt) -> BigInt {
+        rhs -= self;
+        -rhs
     }
 }
 
-impl Rem<u64> for BigUInt {
-    type Output = Self;
-    fn rem(self, rhs: u64) -> Self::Output {
-        let (_, rem) = self.div_rem(Self::from(rhs));
-        rem
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn div(self, rhs: &BigInt) -> BigInt {
+        let (div, _) = self.div_rem_ref(rhs);
+        div
     }
 }
 
-impl RemAssign<u64> for BigUInt {
-    fn rem_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ % rhs);
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        let (_, rem) = self.div_rem_ref(rhs);
+        rem
     }
 }
 
-impl ShlAssign<u32> for BigUInt {
-    fn shl_assign(&mut self, rhs: u32) {
-        replace_with(self, || unreachable!(), |self_| self_ << rhs);
+impl MulAssign<&BigInt> for BigInt {
+    fn mul_assign(&mut self, rhs: &BigInt) {
+        if rhs.sign == Sign::Minus {
+            self.sign = -self.sign;
+        }
+        self.inner *= &rhs.inner;
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
+        }
     }
 }
 
-impl ShrAssign<u32> for BigUInt {
-    fn shr_assign(&mut self, rhs: u32) {
-        replace_with(self, || unreachable!(), |self_| self_ >> rhs);
+// truncated division like `div_rem`
+impl DivAssign<&BigInt> for BigInt {
+    fn div_assign(&mut self, rhs: &BigInt) {
+        if rhs.sign == Sign::Minus {
+            self.sign = -self.sign;
+        }
+        self.inner /= &rhs.inner;
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
+        }
     }
 }
 
-// -----------------------------------------------------------------------------
-
-impl AddAssign for BigInt {
-    fn add_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ + rhs);
+// the remainder has the sign of the dividend
+impl RemAssign<&BigInt> for BigInt {
+    fn rem_assign(&mut self, rhs: &BigInt) {
+        self.inner %= &rhs.inner;
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
+        }
     }
 }
 
 impl Add<u64> for BigInt {
     type Output = Self;
-    fn add(self, rhs: u64) -> Self::Output {
-        self + Self::from(rhs)
+    fn add(mut self, rhs: u64) -> Self::Output {
+        self += rhs;
+        self
     }
 }
 
 impl AddAssign<u64> for BigInt {
     fn add_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ + rhs);
-    }
-}
-
-impl SubAssign for BigInt {
-    fn sub_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ - rhs);
+        *self += &Self::from(rhs);
     }
 }
 
 impl Sub<u64> for BigInt {
     type Output = Self;
-    fn sub(self, rhs: u64) -> Self::Output {
-        self - Self::from(rhs)
+    fn sub(mut self, rhs: u64) -> Self::Output {
+        self -= rhs;
+        self
     }
 }
 
 impl SubAssign<u64> for BigInt {
     fn sub_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ - rhs);
-    }
-}
-
-impl MulAssign for BigInt {
-    fn mul_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ * rhs);
+        *self -= &Self::from(rhs);
     }
 }
 
 impl Mul<u64> for BigInt {
     type Output = Self;
-    fn mul(self, rhs: u64) -> Self::Output {
-        self * Self::from(rhs)
+    fn mul(mut self, rhs: u64) -> Self::Output {
+        self *= rhs;
+        self
     }
 }
 
 impl MulAssign<u64> for BigInt {
     fn mul_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ * rhs);
-    }
-}
-
-impl Div for BigInt {
-    type Output = Self;
-    fn div(self, rhs: Self) -> Self::Output {
-        let (div, _) = self.div_rem(rhs);
-        div
+        self.inner *= rhs;
+        if self.inner.is_zero() {
+            self.sign = Sign::Plus;
+        }
     }
 }
 
@@ -205,52 +553,167 @@ impl Div<u64> for BigInt {
 
 impl DivAssign<u64> for BigInt {
     fn div_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ / rhs);
-    }
-}
-
-impl DivAssign for BigInt {
-    fn div_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ / rhs);
-    }
-}
-
-impl Rem for BigInt {
-    type Output = Self;
-    fn rem(self, rhs: Self) -> Self::Output {
-        let (_, rem) = self.div_rem(rhs);
-        rem
-    }
-}
-
-impl RemAssign for BigInt {
-    fn rem_assign(&mut self, rhs: Self) {
-        replace_with(self, || unreachable!(), |self_| self_ % rhs);
+        *self /= Self::from(rhs);
     }
 }
 
 impl Rem<u64> for BigInt {
     type Output = Self;
     fn rem(self, rhs: u64) -> Self::Output {
-        let (_, rem) = self.div_rem(Self::from(rhs));
-        rem
+        self % Self::from(rhs)
     }
 }
 
 impl RemAssign<u64> for BigInt {
     fn rem_assign(&mut self, rhs: u64) {
-        replace_with(self, || unreachable!(), |self_| self_ % rhs);
-    }
-}
-
-impl ShlAssign<u32> for BigInt {
-    fn shl_assign(&mut self, rhs: u32) {
-        replace_with(self, || unreachable!(), |self_| self_ << rhs);
-    }
-}
-
-impl ShrAssign<u32> for BigInt {
-    fn shr_assign(&mut self, rhs: u32) {
-        replace_with(self, || unreachable!(), |self_| self_ >> rhs);
+        *self %= Self::from(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_operands_test() {
+        let a = BigUInt::from(vec![u64::MAX, 7, 1]);
+        let b = BigUInt::from(vec![5, u64::MAX]);
+        let sum = BigUInt::from(vec![4, 7, 2]);
+        let diff = BigUInt::from(vec![u64::MAX - 5, 8]);
+
+        assert_eq!(&a + &b, sum);
+        assert_eq!(a.clone() + &b, sum);
+        assert_eq!(&a + b.clone(), sum);
+        assert_eq!(&a - &b, diff);
+        assert_eq!(a.clone() - &b, diff);
+        assert_eq!(&a - b.clone(), diff);
+        assert_eq!(&a * &b, a.clone() * b.clone());
+        assert_eq!(&a * b.clone(), a.clone() * b.clone());
+        assert_eq!(&a / &b, a.clone() / b.clone());
+        assert_eq!(&a % b.clone(), a.clone() % b.clone());
+        assert_eq!(&a << 70, a.clone() << 70);
+        assert_eq!(&a >> 70, BigUInt::from(1u64 << 58));
+
+        let mut c = a.clone();
+        c -= &b;
+        c += &b;
+        c *= &b;
+        c /= &b;
+        assert_eq!(c, a);
+
+        let a = BigInt::from(-123_456_789);
+        let b = BigInt::from(1_000);
+        assert_eq!(&a + &b, BigInt::from(-123_455_789));
+        assert_eq!(&b - a.clone(), BigInt::from(123_457_789));
+        assert_eq!(&a - b.clone(), BigInt::from(-123_457_789));
+        assert_eq!(&b - b.clone(), BigInt::from(0));
+        assert_eq!(&a * &b, BigInt::from(-123_456_789_000i64));
+        assert_eq!(&a * BigInt::from(0), BigInt::from(0));
+        assert_eq!(&a / &b, BigInt::from(-123_456));
+        assert_eq!(&a % &b, BigInt::from(-789));
+        assert_eq!(&a >> 40, BigInt::from(-1));
+    }
+
+    #[test]
+    fn assign_in_place_test() {
+        let values = [
+            BigUInt::from(0u64),
+            BigUInt::from(7u64),
+            BigUInt::from(u64::MAX),
+            BigUInt::from(vec![5, u64::MAX]),
+            BigUInt::from(vec![u64::MAX, 7, 1]),
+            BigUInt::from(vec![3; 40]),
+        ];
+        for a in &values {
+            for b in &values {
+                let mut c = a.clone();
+                c *= b;
+                assert_eq!(c, crate::mul::mul(&a.0, &b.0), "{a} * {b}");
+                if b.is_zero() {
+                    continue;
+                }
+                let (div, rem) = crate::div::div_rem(a, b);
+                let mut c = a.clone();
+                c /= b;
+                assert_eq!(c, div, "{a} / {b}");
+                let mut c = a.clone();
+                c %= b;
+                assert_eq!(c, rem, "{a} % {b}");
+            }
+        }
+
+        for (a, b) in [(-7, 2), (7, -2), (-7, -2), (6, -3), (-1, 5), (0, -4)] {
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+            let mut c = x.clone();
+            c *= &y;
+            assert_eq!(c, BigInt::from(a * b));
+            let mut c = x.clone();
+            c /= &y;
+            assert_eq!(c, BigInt::from(a / b));
+            let mut c = x.clone();
+            c %= &y;
+            assert_eq!(c, BigInt::from(a % b));
+            assert_eq!(c.sign == Sign::Minus, a % b < 0, "{a} % {b}");
+        }
+    }
+
+    #[test]
+    fn sum_product_test() {
+        let values: Vec<BigUInt> = (1..=30u64).map(BigUInt::from).collect();
+        assert_eq!(values.iter().sum::<BigUInt>(), BigUInt::from(465u64));
+        assert_eq!(
+            values.iter().product::<BigUInt>(),
+            crate::combinatorics::factorial(30)
+        );
+        assert_eq!(values.into_iter().sum::<BigUInt>(), BigUInt::from(465u64));
+        assert_eq!(
+            std::iter::empty::<BigUInt>().sum::<BigUInt>(),
+            BigUInt::ZERO
+        );
+        assert_eq!(
+            std::iter::empty::<&BigInt>().product::<BigInt>(),
+            BigInt::ONE
+        );
+
+        let signed = [BigInt::from(-3), BigInt::from(5), BigInt::from(-7)];
+        assert_eq!(signed.iter().sum::<BigInt>(), BigInt::from(-5));
+        assert_eq!(signed.into_iter().product::<BigInt>(), BigInt::from(105));
+
+        let halves = (1..=4).map(|k| BigRational::new(BigInt::from(1), BigInt::from(1 << k)));
+        assert_eq!(
+            halves.sum::<BigRational>(),
+            BigRational::new(BigInt::from(15), BigInt::from(16))
+        );
+        let decimals: Vec<BigDecimal> = ["1.5", "2.25", "-0.5"].map(|s| s.parse().unwrap()).into();
+        assert_eq!(decimals.iter().sum::<BigDecimal>().to_string(), "3.25");
+        assert_eq!(
+            decimals.into_iter().product::<BigDecimal>().to_string(),
+            "-1.6875"
+        );
+    }
+
+    #[test]
+    fn u64_operands_test() {
+        let mut a = BigUInt::from(vec![u64::MAX, u64::MAX]);
+        a += 1;
+        assert_eq!(a, BigUInt::from(vec![0, 0, 1]));
+        a -= 1;
+        assert_eq!(a, BigUInt::from(vec![u64::MAX, u64::MAX]));
+        a *= 3;
+        assert_eq!(a, BigUInt::from(vec![u64::MAX - 2, u64::MAX, 2]));
+        a /= 3;
+        assert_eq!(a, BigUInt::from(vec![u64::MAX, u64::MAX]));
+        a %= 10;
+        assert_eq!(a, BigUInt::from(5u64));
+        a *= 0;
+        assert_eq!(a, BigUInt::from(0u64));
+
+        let mut b = BigInt::from(-5);
+        b += 10;
+        assert_eq!(b, BigInt::from(5));
+        b -= 12;
+        assert_eq!(b, BigInt::from(-7));
+        b *= 0;
+        assert_eq!(b, BigInt::from(0));
     }
 }
@@ -0,0 +1,345 @@
+use std::fmt::Display;
+
+use super::{BigInt, BigUInt, Pow, Sign};
+
+// error of a non-panicking counterpart of an arithmetic operation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticError {
+    // the result of an unsigned subtraction would be negative
+    Underflow,
+    DivisionByZero,
+    // the result would have more than `limit` bits
+    TooLarge { limit: u64 },
+    // modular arithmetic on `BigInt` and even roots require non-negative operands
+    NegativeOperand,
+    // Montgomery reduction requires an odd modulus
+    EvenModulus,
+    ZerothRoot,
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::Underflow => write!(f, "cannot subtract lower number from bigger"),
+            ArithmeticError::DivisionByZero => write!(f, "division by zero"),
+            ArithmeticError::TooLarge { limit } => {
+                write!(f, "result doesn't fit into the limit of {limit} bits")
+            }
+            ArithmeticError::NegativeOperand => {
+                write!(f, "operation requires non-negative operands")
+            }
+            ArithmeticError::EvenModulus => {
+                write!(f, "Montgomery reduction requires an odd modulus")
+            }
+            ArithmeticError::ZerothRoot => write!(f, "zeroth root is undefined"),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+// There are no wrapping operations: `BigUInt` and `BigInt` grow instead of overflowing,
+// and an unsigned result below zero has no modulus to wrap around.
+// Fixed-width wrapping arithmetic is provided by `ct::ConstUInt`
+
+fn check_divisor(rhs: &BigUInt) -> Result<(), ArithmeticError> {
+    if rhs.is_zero() {
+        Err(ArithmeticError::DivisionByZero)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_non_negative(value: &BigInt) -> Result<&BigUInt, ArithmeticError> {
+    match value.sign {
+        Sign::Plus => Ok(&value.inner),
+        Sign::Minus => Err(ArithmeticError::NegativeOperand),
+    }
+}
+
+impl BigUInt {
+    pub fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if self < rhs {
+            return Err(ArithmeticError::Underflow);
+        }
+        Ok(self - rhs)
+    }
+
+    // zero instead of a negative result
+    #[must_use]
+    pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_else(|_| Self::from(0u64))
+    }
+
+    #[must_use]
+    pub fn abs_diff(&self, rhs: &Self) -> Self {
+        if self >= rhs {
+            self - rhs
+        } else {
+            rhs - self
+        }
+    }
+
+    pub fn checked_sub_u64(&self, rhs: u64) -> Result<Self, ArithmeticError> {
+        if *self < rhs {
+            return Err(ArithmeticError::Underflow);
+        }
+        Ok(self.clone() - rhs)
+    }
+
+    pub fn checked_div_rem(&self, rhs: &Self) -> Result<(Self, Self), ArithmeticError> {
+        check_divisor(rhs)?;
+        Ok(crate::div::div_rem(self, rhs))
+    }
+
+    pub fn checked_div_rem_u64(&self, rhs: u64) -> Result<(Self, u64), ArithmeticError> {
+        if rhs == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(self.clone().div_rem_u64(rhs))
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(rhs)?;
+        Ok(self / rhs)
+    }
+
+    pub fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(rhs)?;
+        Ok(self % rhs)
+    }
+
+    pub fn checked_div_u64(&self, rhs: u64) -> Result<Self, ArithmeticError> {
+        Ok(self.checked_div_rem_u64(rhs)?.0)
+    }
+
+    pub fn checked_rem_u64(&self, rhs: u64) -> Result<u64, ArithmeticError> {
+        Ok(self.checked_div_rem_u64(rhs)?.1)
+    }
+
+    // `self^exp` unless it has more than `max_bits` bits
+    pub fn checked_pow(&self, exp: u64, max_bits: u64) -> Result<Self, ArithmeticError> {
+        let too_large = Err(ArithmeticError::TooLarge { limit: max_bits });
+
+        // a b-bit number raised to `exp` has from (b - 1) * exp + 1 to b * exp bits,
+        // the exact size is only known after the computation
+        let bits = u64::from(self.bits_num());
+        if bits > 1 && exp > 0 {
+            match (bits - 1).checked_mul(exp) {
+                Some(lower) if lower < max_bits => {}
+                _ => return too_large,
+            }
+        }
+
+        let power = self.clone().pow(exp);
+        if u64::from(power.bits_num()) > max_bits {
+            return too_large;
+        }
+        Ok(power)
+    }
+
+    pub fn checked_modmul(&self, rhs: &Self, modulus: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(modulus)?;
+        Ok(self.modmul(rhs, modulus))
+    }
+
+    pub fn checked_modpow(&self, exp: &Self, modulus: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(modulus)?;
+        Ok(self.modpow(exp, modulus))
+    }
+
+    // `Ok(None)` when the inverse doesn't exist
+    pub fn checked_modinv(&self, modulus: &Self) -> Result<Option<Self>, ArithmeticError> {
+        check_divisor(modulus)?;
+        Ok(self.modinv(modulus))
+    }
+
+    pub fn checked_nth_root(&self, n: u32) -> Result<Self, ArithmeticError> {
+        if n == 0 {
+            return Err(ArithmeticError::ZerothRoot);
+        }
+        Ok(self.nth_root(n))
+    }
+}
+
+impl BigInt {
+    // truncated division like `div_rem`
+    pub fn checked_div_rem(&self, rhs: &Self) -> Result<(Self, Self), ArithmeticError> {
+        check_divisor(&rhs.inner)?;
+        Ok(self.div_rem_ref(rhs))
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(&rhs.inner)?;
+        Ok(self / rhs)
+    }
+
+    pub fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        check_divisor(&rhs.inner)?;
+        Ok(self % rhs)
+    }
+
+    // `max_bits` limits the absolute value of the result
+    pub fn checked_pow(&self, exp: u64, max_bits: u64) -> Result<Self, ArithmeticError> {
+        let inner = self.inner.checked_pow(exp, max_bits)?;
+        let sign = if exp.is_multiple_of(2) {
+            Sign::Plus
+        } else {
+            self.sign
+        };
+        Ok(Self::from((inner, sign)).fix_zero())
+    }
+
+    pub fn checked_modmul(&self, rhs: &Self, modulus: &Self) -> Result<Self, ArithmeticError> {
+        let (lhs, rhs) = (check_non_negative(self)?, check_non_negative(rhs)?);
+        let modulus = check_non_negative(modulus)?;
+        lhs.checked_modmul(rhs, modulus).map(Self::from)
+    }
+
+    pub fn checked_modpow(&self, exp: &Self, modulus: &Self) -> Result<Self, ArithmeticError> {
+        let (base, exp) = (check_non_negative(self)?, check_non_negative(exp)?);
+        let modulus = check_non_negative(modulus)?;
+        base.checked_modpow(exp, modulus).map(Self::from)
+    }
+
+    // `self` may be negative like in `modinv`, `Ok(None)` when the inverse doesn't exist
+    pub fn checked_modinv(&self, modulus: &Self) -> Result<Option<Self>, ArithmeticError> {
+        check_divisor(check_non_negative(modulus)?)?;
+        Ok(self.modinv(modulus))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modular::{BarrettContext, MontgomeryContext};
+
+    #[test]
+    fn sub_test() {
+        let (a, b) = (BigUInt::from(5u64), BigUInt::from(u64::MAX) << 64);
+        assert_eq!(a.checked_sub(&b), Err(ArithmeticError::Underflow));
+        assert_eq!(b.checked_sub(&a), Ok(&b - &a));
+        assert_eq!(a.saturating_sub(&b), BigUInt::from(0u64));
+        assert_eq!(b.saturating_sub(&a), &b - &a);
+        assert_eq!(a.abs_diff(&b), &b - &a);
+        assert_eq!(b.abs_diff(&a), &b - &a);
+        assert_eq!(a.abs_diff(&a), BigUInt::from(0u64));
+    }
+
+    #[test]
+    fn div_test() {
+        let (a, zero) = (BigUInt::from(17u64), BigUInt::from(0u64));
+        assert_eq!(a.checked_div(&zero), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.checked_rem(&zero), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(
+            a.checked_div_rem(&zero),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            a.checked_modpow(&a, &zero),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            a.checked_div_rem(&BigUInt::from(5u64)),
+            Ok((BigUInt::from(3u64), BigUInt::from(2u64)))
+        );
+
+        let (a, zero) = (BigInt::from(-17), BigInt::from(0));
+        assert_eq!(a.checked_div(&zero), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.checked_rem(&zero), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.checked_div(&BigInt::from(5)), Ok(BigInt::from(-3)));
+        assert_eq!(a.checked_rem(&BigInt::from(5)), Ok(BigInt::from(-2)));
+    }
+
+    #[test]
+    fn pow_test() {
+        let three = BigUInt::from(3u64);
+        // 3^40 has 64 bits
+        assert_eq!(three.checked_pow(40, 64), Ok(BigUInt::from(3u64.pow(40))));
+        assert_eq!(
+            three.checked_pow(41, 64),
+            Err(ArithmeticError::TooLarge { limit: 64 })
+        );
+        // rejected without computing anything
+        assert_eq!(
+            three.checked_pow(u64::MAX, 1 << 20),
+            Err(ArithmeticError::TooLarge { limit: 1 << 20 })
+        );
+        assert_eq!(
+            BigUInt::from(1u64).checked_pow(u64::MAX, 1),
+            Ok(BigUInt::from(1u64))
+        );
+        assert_eq!(
+            BigUInt::from(0u64).checked_pow(u64::MAX, 0),
+            Ok(BigUInt::from(0u64))
+        );
+        assert_eq!(three.checked_pow(0, 1), Ok(BigUInt::from(1u64)));
+
+        assert_eq!(BigInt::from(-3).checked_pow(3, 8), Ok(BigInt::from(-27)));
+        assert_eq!(BigInt::from(-3).checked_pow(2, 8), Ok(BigInt::from(9)));
+    }
+
+    #[test]
+    fn counterparts_test() {
+        let (a, zero) = (BigUInt::from(17u64), BigUInt::from(0u64));
+        assert_eq!(a.checked_sub_u64(18), Err(ArithmeticError::Underflow));
+        assert_eq!(a.checked_sub_u64(17), Ok(zero.clone()));
+        assert_eq!(a.checked_div_u64(0), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.checked_rem_u64(0), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(a.checked_div_rem_u64(5), Ok((BigUInt::from(3u64), 2)));
+        assert_eq!(
+            a.checked_modmul(&a, &zero),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            a.checked_modinv(&zero),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(a.checked_modinv(&BigUInt::from(34u64)), Ok(None));
+        assert_eq!(a.checked_nth_root(0), Err(ArithmeticError::ZerothRoot));
+        assert_eq!(a.checked_nth_root(2), Ok(BigUInt::from(4u64)));
+
+        let (a, m) = (BigInt::from(3), BigInt::from(7));
+        let negative = BigInt::from(-7);
+        let error = Err(ArithmeticError::NegativeOperand);
+        assert_eq!(a.checked_modmul(&a, &m), Ok(BigInt::from(2)));
+        assert_eq!(negative.checked_modmul(&a, &m), error);
+        assert_eq!(a.checked_modpow(&negative, &m), error);
+        assert_eq!(a.checked_modpow(&a, &negative), error);
+        assert_eq!(
+            a.checked_modinv(&negative),
+            Err(ArithmeticError::NegativeOperand)
+        );
+        assert_eq!(
+            a.checked_modinv(&BigInt::from(0)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+        assert_eq!(
+            BigInt::from(-3).checked_modinv(&m),
+            Ok(Some(BigInt::from(2)))
+        );
+        assert_eq!(a.checked_nth_root(0), Err(ArithmeticError::ZerothRoot));
+
+        assert_eq!(
+            MontgomeryContext::try_new(BigUInt::from(10u64)).unwrap_err(),
+            ArithmeticError::EvenModulus
+        );
+        assert!(MontgomeryContext::try_new(BigUInt::from(11u64)).is_ok());
+        assert_eq!(
+            BarrettContext::try_new(zero).unwrap_err(),
+            ArithmeticError::DivisionByZero
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            ArithmeticError::DivisionByZero.to_string(),
+            "division by zero"
+        );
+        assert_eq!(
+            ArithmeticError::TooLarge { limit: 64 }.to_string(),
+            "result doesn't fit into the limit of 64 bits"
+        );
+    }
+}
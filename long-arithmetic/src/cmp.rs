@@ -1,4 +1,5 @@
 use super::{BigInt, BigUInt, Sign};
+use crate::convert::{to_i128, to_u128};
 use std::cmp::Ordering;
 
 impl Ord for BigUInt {
@@ -46,6 +47,108 @@ impl PartialOrd for BigInt {
     }
 }
 
+// Comparisons with primitive integers without converting them to big numbers.
+// Every primitive fits into `u128` or `i128`, bigger values are beyond its range
+
+fn cmp_biguint_u128(lhs: &BigUInt, rhs: u128) -> Ordering {
+    to_u128(lhs).map_or(Ordering::Greater, |lhs| lhs.cmp(&rhs))
+}
+
+fn cmp_biguint_i128(lhs: &BigUInt, rhs: i128) -> Ordering {
+    if rhs < 0 {
+        return Ordering::Greater;
+    }
+    cmp_biguint_u128(lhs, rhs as u128)
+}
+
+fn cmp_bigint_u128(lhs: &BigInt, rhs: u128) -> Ordering {
+    match lhs.sign {
+        Sign::Plus => cmp_biguint_u128(&lhs.inner, rhs),
+        Sign::Minus => Ordering::Less,
+    }
+}
+
+fn cmp_bigint_i128(lhs: &BigInt, rhs: i128) -> Ordering {
+    match (to_i128(lhs), lhs.sign) {
+        (Some(lhs), _) => lhs.cmp(&rhs),
+        (None, Sign::Plus) => Ordering::Greater,
+        (None, Sign::Minus) => Ordering::Less,
+    }
+}
+
+macro_rules! impl_cmp_primitive {
+    ($big:ty, $wide:ty, $cmp:ident, $($t:ty),*) => {$(
+        impl PartialEq<$t> for $big {
+            fn eq(&self, other: &$t) -> bool {
+                $cmp(self, *other as $wide) == Ordering::Equal
+            }
+        }
+
+        impl PartialOrd<$t> for $big {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                Some($cmp(self, *other as $wide))
+            }
+        }
+
+        impl PartialEq<$big> for $t {
+            fn eq(&self, other: &$big) -> bool {
+                $cmp(other, *self as $wide) == Ordering::Equal
+            }
+        }
+
+        impl PartialOrd<$big> for $t {
+            fn partial_cmp(&self, other: &$big) -> Option<Ordering> {
+                Some($cmp(other, *self as $wide).reverse())
+            }
+        }
+    )*};
+}
+
+impl_cmp_primitive!(
+    BigUInt,
+    u128,
+    cmp_biguint_u128,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize
+);
+impl_cmp_primitive!(
+    BigUInt,
+    i128,
+    cmp_biguint_i128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize
+);
+impl_cmp_primitive!(
+    BigInt,
+    u128,
+    cmp_bigint_u128,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize
+);
+impl_cmp_primitive!(
+    BigInt,
+    i128,
+    cmp_bigint_i128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +190,76 @@ mod tests {
         let second = "-7890123213".parse::<BigInt>().unwrap();
         assert_eq!(first.cmp(&second), Ordering::Equal);
     }
+
+    #[test]
+    fn primitive_cmp_test() {
+        let max = BigUInt::from(u64::MAX);
+        let big = BigUInt::from(u128::MAX) + 1;
+        assert!(BigUInt::ZERO == 0u8 && 0i64 == BigUInt::ZERO);
+        assert!(max == u64::MAX && max != u64::MAX - 1);
+        assert!(BigUInt::ZERO > -1 && BigUInt::ONE >= 1i8 && BigUInt::ONE < 2usize);
+        assert_eq!(max.partial_cmp(&-7), Some(Ordering::Greater));
+        assert_eq!(big.partial_cmp(&u128::MAX), Some(Ordering::Greater));
+        assert_eq!(u128::MAX.partial_cmp(&big), Some(Ordering::Less));
+        assert_ne!(big, u128::MAX);
+
+        let positive = BigInt::from(big);
+        let negative = -positive.clone();
+        assert_eq!(positive.partial_cmp(&i128::MAX), Some(Ordering::Greater));
+        assert_eq!(u128::MAX.partial_cmp(&positive), Some(Ordering::Less));
+        assert_eq!(negative.partial_cmp(&i128::MIN), Some(Ordering::Less));
+        assert_eq!(i128::MIN.partial_cmp(&negative), Some(Ordering::Greater));
+        assert_eq!(negative.partial_cmp(&0u8), Some(Ordering::Less));
+
+        let min = BigInt::from(i128::MIN);
+        let five = BigInt::from(-5);
+        assert!(min == i128::MIN && min < i128::MIN + 1 && min < 0u128);
+        assert!(five == -5i32 && five < -4 && -6 < five && five < 0u64 && 5u64 > five);
+        assert_eq!(BigInt::ZERO.partial_cmp(&0isize), Some(Ordering::Equal));
+    }
+
+    fn hash<T: std::hash::Hash>(value: &T) -> u64 {
+        use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher};
+        BuildHasherDefault::<DefaultHasher>::default().hash_one(value)
+    }
+
+    #[test]
+    fn hash_default_test() {
+        use crate::decimal::BigDecimal;
+        use crate::rational::BigRational;
+
+        assert_eq!(BigUInt::default(), BigUInt::ZERO);
+        assert_eq!(BigUInt::ONE, BigUInt::from(1u64));
+        assert_eq!(BigInt::default(), BigInt::ZERO);
+        assert_eq!(BigInt::ONE, BigInt::from(1));
+        assert_eq!(BigRational::default(), BigRational::ZERO);
+        assert_eq!(BigDecimal::default(), BigDecimal::ZERO);
+        assert_eq!(BigDecimal::ONE, BigDecimal::new(BigInt::from(100), 2));
+
+        // equal values from different computations hash alike
+        let a = BigUInt::from(vec![1, 2, 3]);
+        let b = (a.clone() << 200) >> 200;
+        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash(&(BigInt::from(-5) + 5)), hash(&BigInt::ZERO));
+        assert_eq!(hash(&-BigInt::ZERO), hash(&BigInt::default()));
+        assert_ne!(hash(&BigInt::from(-5)), hash(&BigInt::from(5)));
+        assert_eq!(
+            hash(&BigRational::new(BigInt::from(6), BigInt::from(-4))),
+            hash(&BigRational::new(BigInt::from(-3), BigInt::from(2)))
+        );
+        let (x, y) = (
+            BigDecimal::new(BigInt::from(1500), 3),
+            BigDecimal::new(BigInt::from(15), 1),
+        );
+        assert_eq!(x, y);
+        assert_eq!(hash(&x), hash(&y));
+        assert_eq!(
+            hash(&BigDecimal::new(BigInt::ZERO, 7)),
+            hash(&BigDecimal::ZERO)
+        );
+
+        let set: std::collections::HashSet<BigInt> =
+            [-2, -1, 0, 1, 2].map(|v: i32| BigInt::from(v * v)).into();
+        assert_eq!(set.len(), 3);
+    }
 }
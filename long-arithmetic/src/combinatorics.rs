@@ -0,0 +1,269 @@
+use super::BigUInt;
+
+// products with fewer factors are accumulated limb by limb
+const PRODUCT_LEAF: usize = 16;
+
+// binomial coefficients with `k` of at least n / BINOMIAL_SIEVE_RATIO are built from a
+// sieve of primes up to `n`, smaller `k` use the product of k numbers divided by k!.
+// The sieve costs O(n) however small `k` is, the crossover drifts from about n / 12 for
+// n = 1000 to n / 256 for n = 10^7
+const BINOMIAL_SIEVE_RATIO: u64 = 64;
+
+// below this `k` the product is always faster
+const BINOMIAL_SIEVE_MIN_K: u64 = 32;
+
+// primes up to `n`
+fn sieve(n: u64) -> Vec<u64> {
+    if n < 2 {
+        return vec![];
+    }
+
+    // odd numbers only, index i stands for 2i + 1
+    let len = (n as usize).div_ceil(2);
+    let mut composite = vec![false; len];
+    let mut i = 1;
+    while (2 * i + 1) * (2 * i + 1) <= n as usize {
+        if !composite[i] {
+            let p = 2 * i + 1;
+            for j in (p * p / 2..len).step_by(p) {
+                composite[j] = true;
+            }
+        }
+        i += 1;
+    }
+
+    let odd = (1..len)
+        .filter(|&i| !composite[i])
+        .map(|i| 2 * i as u64 + 1);
+    std::iter::once(2).chain(odd).collect()
+}
+
+// balanced product tree, so that the big multiplications get operands of similar size
+fn product(values: &[u64]) -> BigUInt {
+    if values.len() <= PRODUCT_LEAF {
+        let mut result = BigUInt::from(1u64);
+        for &value in values {
+            result *= value;
+        }
+        return result;
+    }
+
+    let (low, high) = values.split_at(values.len() / 2);
+    &product(low) * &product(high)
+}
+
+// product of p^e over the (prime, exponent) pairs. Bits of the exponents are processed
+// from the highest: each step squares the result and multiplies in the primes whose
+// exponent has the current bit set
+fn from_exponents(factors: &[(u64, u64)]) -> BigUInt {
+    let Some(max) = factors.iter().map(|&(_, e)| e).max() else {
+        return BigUInt::from(1u64);
+    };
+
+    let mut result = BigUInt::from(1u64);
+    for bit in (0..u64::BITS - max.leading_zeros()).rev() {
+        result = result.square();
+        let primes: Vec<u64> = factors
+            .iter()
+            .filter(|&&(_, e)| e >> bit & 1 == 1)
+            .map(|&(p, _)| p)
+            .collect();
+        result = &result * &product(&primes);
+    }
+    result
+}
+
+// exponent of the prime `p` in n! by Legendre's formula
+fn legendre(mut n: u64, p: u64) -> u64 {
+    let mut exp = 0;
+    while n >= p {
+        n /= p;
+        exp += n;
+    }
+    exp
+}
+
+// n!
+#[must_use]
+pub fn factorial(n: u64) -> BigUInt {
+    if n < 2 {
+        return BigUInt::from(1u64);
+    }
+
+    // the power of two is a shift, n - popcount(n) = legendre(n, 2)
+    let factors: Vec<(u64, u64)> = sieve(n)[1..].iter().map(|&p| (p, legendre(n, p))).collect();
+    from_exponents(&factors) << (n - u64::from(n.count_ones())) as u32
+}
+
+// n!! = n * (n - 2) * (n - 4) * ... down to 1 or 2
+#[must_use]
+pub fn double_factorial(n: u64) -> BigUInt {
+    if n.is_multiple_of(2) {
+        // (2k)!! = 2^k * k!
+        return factorial(n / 2) << (n / 2) as u32;
+    }
+
+    let odd: Vec<u64> = (1..=n).step_by(2).collect();
+    product(&odd)
+}
+
+// number of k-element subsets of an n-element set, zero for k > n
+#[must_use]
+pub fn binomial(n: u64, k: u64) -> BigUInt {
+    if k > n {
+        return BigUInt::from(0u64);
+    }
+    let k = k.min(n - k);
+
+    if k < BINOMIAL_SIEVE_MIN_K || k < n / BINOMIAL_SIEVE_RATIO {
+        let numerator: Vec<u64> = (n - k + 1..=n).collect();
+        return product(&numerator) / factorial(k);
+    }
+
+    // the exponent of p is the number of borrows when subtracting k from n in base p (Kummer)
+    let factors: Vec<(u64, u64)> = sieve(n)
+        .into_iter()
+        .map(|p| (p, legendre(n, p) - legendre(k, p) - legendre(n - k, p)))
+        .filter(|&(_, e)| e > 0)
+        .collect();
+    from_exponents(&factors)
+}
+
+// (F(n), F(n + 1)) by fast doubling:
+// F(2k) = F(k) * (2 * F(k + 1) - F(k)), F(2k + 1) = F(k)^2 + F(k + 1)^2
+fn fibonacci_pair(n: u64) -> (BigUInt, BigUInt) {
+    let (mut a, mut b) = (BigUInt::from(0u64), BigUInt::from(1u64));
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let even = &a * &((b.clone() << 1) - &a);
+        let odd = a.square() + b.square();
+        (a, b) = if n >> bit & 1 == 0 {
+            (even, odd)
+        } else {
+            let next = &even + &odd;
+            (odd, next)
+        };
+    }
+    (a, b)
+}
+
+// F(0) = 0, F(1) = 1, F(n) = F(n - 1) + F(n - 2)
+#[must_use]
+pub fn fibonacci(n: u64) -> BigUInt {
+    fibonacci_pair(n).0
+}
+
+// L(0) = 2, L(1) = 1, L(n) = L(n - 1) + L(n - 2)
+#[must_use]
+pub fn lucas(n: u64) -> BigUInt {
+    // L(n) = F(n - 1) + F(n + 1) = 2 * F(n + 1) - F(n)
+    let (f, next) = fibonacci_pair(n);
+    (next << 1) - f
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_factorial(n: u64) -> BigUInt {
+        (1..=n).fold(BigUInt::from(1u64), |acc, k| acc * BigUInt::from(k))
+    }
+
+    #[test]
+    fn sieve_test() {
+        assert_eq!(sieve(1), Vec::<u64>::new());
+        assert_eq!(sieve(2), vec![2]);
+        assert_eq!(sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(sieve(31).last(), Some(&31));
+        assert_eq!(sieve(1_000_000).len(), 78_498);
+    }
+
+    #[test]
+    fn factorial_test() {
+        assert_eq!(factorial(0), BigUInt::from(1u64));
+        assert_eq!(factorial(20), BigUInt::from(2_432_902_008_176_640_000u64));
+        for n in [1, 2, 3, 25, 100, 257, 1000] {
+            assert_eq!(factorial(n), naive_factorial(n), "{n}!");
+        }
+        assert_eq!(
+            factorial(50).to_string(),
+            "30414093201713378043612608166064768844377641568960512000000000000"
+        );
+    }
+
+    #[test]
+    fn double_factorial_test() {
+        let expected = [1u64, 1, 2, 3, 8, 15, 48, 105, 384, 945, 3840];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!(double_factorial(n as u64), BigUInt::from(value), "{n}!!");
+        }
+        // n!! * (n - 1)!! = n!
+        for n in [100, 101, 999] {
+            assert_eq!(
+                &double_factorial(n) * &double_factorial(n - 1),
+                factorial(n)
+            );
+        }
+    }
+
+    #[test]
+    fn binomial_test() {
+        assert_eq!(binomial(5, 2), BigUInt::from(10u64));
+        assert_eq!(binomial(5, 6), BigUInt::from(0u64));
+        assert_eq!(binomial(0, 0), BigUInt::from(1u64));
+        assert_eq!(
+            binomial(u64::MAX, 2),
+            BigUInt::from(u128::from(u64::MAX) * u128::from(u64::MAX - 1) / 2)
+        );
+
+        // both algorithms against the factorial definition and Pascal's rule
+        for (n, k) in [(100, 50), (200, 31), (200, 32), (1000, 333), (1000, 999)] {
+            let expected = factorial(n) / (factorial(k) * factorial(n - k));
+            assert_eq!(binomial(n, k), expected, "C({n}, {k})");
+            assert_eq!(
+                binomial(n, k),
+                binomial(n - 1, k - 1) + binomial(n - 1, k),
+                "C({n}, {k})"
+            );
+        }
+
+        // C(n, k + 1) * (k + 1) = C(n, k) * (n - k) across the switch between the algorithms
+        let n = 64 * 40;
+        for k in [38, 39, 40, 41] {
+            assert_eq!(
+                binomial(n, k + 1) * (k + 1),
+                binomial(n, k) * (n - k),
+                "C({n}, {k})"
+            );
+        }
+
+        // the row sums to 2^n
+        let n = 300;
+        let sum = (0..=n).fold(BigUInt::from(0u64), |acc, k| acc + binomial(n, k));
+        assert_eq!(sum, BigUInt::from(1u64) << n as u32);
+    }
+
+    #[test]
+    fn fibonacci_test() {
+        let (mut a, mut b) = (BigUInt::from(0u64), BigUInt::from(1u64));
+        for n in 0..300 {
+            assert_eq!(fibonacci(n), a, "F({n})");
+            (a, b) = (b.clone(), a + b);
+        }
+        assert_eq!(
+            fibonacci(1000).to_string(),
+            "43466557686937456435688527675040625802564660517371780402481729089536555417949051890403879840079255169295922593080322634775209689623239873322471161642996440906533187938298969649928516003704476137795166849228875"
+        );
+    }
+
+    #[test]
+    fn lucas_test() {
+        let expected = [2u64, 1, 3, 4, 7, 11, 18, 29, 47, 76];
+        for (n, &value) in expected.iter().enumerate() {
+            assert_eq!(lucas(n as u64), BigUInt::from(value), "L({n})");
+        }
+        // F(2n) = F(n) * L(n)
+        for n in [50, 511, 4096] {
+            assert_eq!(fibonacci(2 * n), &fibonacci(n) * &lucas(n));
+        }
+    }
+}
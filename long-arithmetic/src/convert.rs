@@ -1,5 +1,6 @@
+use super::limbs::Limbs;
 use super::{BigInt, BigUInt, Sign};
-use std::fmt::Display;
+use std::fmt::{Binary, Display, LowerHex, Octal, UpperHex};
 use std::str::FromStr;
 
 impl From<(BigUInt, Sign)> for BigInt {
@@ -8,38 +9,56 @@ impl From<(BigUInt, Sign)> for BigInt {
     }
 }
 
-impl From<u64> for BigUInt {
-    fn from(value: u64) -> Self {
-        match value {
-            0 => Self(vec![]),
-            value => Self(vec![value]),
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigUInt {
+            fn from(value: $t) -> Self {
+                Self::from(value as u128)
+            }
         }
-    }
+
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                Self::from((BigUInt::from(value), Sign::Plus))
+            }
+        }
+    )*};
 }
 
-impl From<u64> for BigInt {
-    fn from(value: u64) -> Self {
-        Self::from((value.into(), Sign::Plus))
-    }
+macro_rules! impl_from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                let sign = if value >= 0 { Sign::Plus } else { Sign::Minus };
+                Self::from((BigUInt::from(value.unsigned_abs()), sign))
+            }
+        }
+    )*};
 }
 
-impl From<i64> for BigInt {
-    fn from(value: i64) -> Self {
-        let sign = if value >= 0 { Sign::Plus } else { Sign::Minus };
-        Self::from((value.unsigned_abs().into(), sign))
+impl From<u128> for BigUInt {
+    fn from(value: u128) -> Self {
+        let (low, high) = (value as u64, (value >> 64) as u64);
+        match (low, high) {
+            (0, 0) => Self(Limbs::new()),
+            (low, 0) => Self(Limbs::from(&[low][..])),
+            (low, high) => Self(Limbs::from(&[low, high][..])),
+        }
     }
 }
 
-impl From<i32> for BigInt {
-    fn from(value: i32) -> Self {
-        let sign = if value >= 0 { Sign::Plus } else { Sign::Minus };
-        Self::from(((value.unsigned_abs() as u64).into(), sign))
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        Self::from((BigUInt::from(value), Sign::Plus))
     }
 }
 
+impl_from_unsigned!(u8, u16, u32, u64, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
 impl From<Vec<u64>> for BigUInt {
     fn from(value: Vec<u64>) -> Self {
-        Self(value)
+        Self(value.into())
     }
 }
 
@@ -49,21 +68,9 @@ impl From<Vec<u64>> for BigInt {
     }
 }
 
-impl TryFrom<&BigUInt> for u64 {
-    type Error = ();
-
-    fn try_from(value: &BigUInt) -> Result<Self, Self::Error> {
-        match value.0.len() {
-            0 => Ok(0),
-            1 => Ok(value.0[0]),
-            _ => Err(()),
-        }
-    }
-}
-
 impl From<BigUInt> for Vec<u64> {
     fn from(value: BigUInt) -> Self {
-        value.0
+        value.0.into_vec()
     }
 }
 
@@ -76,60 +83,436 @@ impl From<BigUInt> for BigInt {
     }
 }
 
+// error of a conversion into a type that can't hold the value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    // too large, too small or negative for an unsigned type
+    OutOfRange,
+    // NaN or an infinity
+    NotFinite,
+    // a float with a fractional part
+    NotIntegral,
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::OutOfRange => write!(f, "value is out of range of the target type"),
+            ConversionError::NotFinite => write!(f, "cannot convert a non-finite float"),
+            ConversionError::NotIntegral => {
+                write!(f, "cannot convert a float with a fractional part")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+pub(crate) fn to_u128(value: &BigUInt) -> Option<u128> {
+    match value.0[..] {
+        [] => Some(0),
+        [low] => Some(u128::from(low)),
+        [low, high] => Some(u128::from(high) << 64 | u128::from(low)),
+        _ => None,
+    }
+}
+
+pub(crate) fn to_i128(value: &BigInt) -> Option<i128> {
+    let magnitude = to_u128(&value.inner)?;
+    match value.sign {
+        Sign::Plus => i128::try_from(magnitude).ok(),
+        Sign::Minus => {
+            (magnitude <= i128::MIN.unsigned_abs()).then(|| (magnitude as i128).wrapping_neg())
+        }
+    }
+}
+
+macro_rules! impl_try_from_big {
+    ($($t:ty),*) => {$(
+        impl TryFrom<&BigUInt> for $t {
+            type Error = ConversionError;
+
+            fn try_from(value: &BigUInt) -> Result<Self, Self::Error> {
+                to_u128(value)
+                    .and_then(|value| <$t>::try_from(value).ok())
+                    .ok_or(ConversionError::OutOfRange)
+            }
+        }
+
+        impl TryFrom<BigUInt> for $t {
+            type Error = ConversionError;
+
+            fn try_from(value: BigUInt) -> Result<Self, Self::Error> {
+                Self::try_from(&value)
+            }
+        }
+
+        impl TryFrom<&BigInt> for $t {
+            type Error = ConversionError;
+
+            fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+                let result = match value.sign {
+                    Sign::Plus => to_u128(&value.inner).and_then(|value| <$t>::try_from(value).ok()),
+                    Sign::Minus => to_i128(value).and_then(|value| <$t>::try_from(value).ok()),
+                };
+                result.ok_or(ConversionError::OutOfRange)
+            }
+        }
+
+        impl TryFrom<BigInt> for $t {
+            type Error = ConversionError;
+
+            fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+                Self::try_from(&value)
+            }
+        }
+    )*};
+}
+
+impl_try_from_big!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 impl TryFrom<BigInt> for BigUInt {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: BigInt) -> Result<Self, Self::Error> {
         match value.sign {
             Sign::Plus => Ok(value.inner),
-            Sign::Minus => Err(()),
+            Sign::Minus => Err(ConversionError::OutOfRange),
+        }
+    }
+}
+
+// (mantissa, exp, sign) with |value| = mantissa * 2^exp, the value must be finite
+pub(crate) fn decode_f64(value: f64) -> (u64, i64, Sign) {
+    let bits = value.to_bits();
+    let biased = ((bits >> 52) & 0x7FF) as i64;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exp) = match biased {
+        0 => (fraction, -1074),
+        _ => (fraction | 1 << 52, biased - 1075),
+    };
+    let sign = if value.is_sign_negative() {
+        Sign::Minus
+    } else {
+        Sign::Plus
+    };
+    (mantissa, exp, sign)
+}
+
+impl TryFrom<f64> for BigInt {
+    type Error = ConversionError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() {
+            return Err(ConversionError::NotFinite);
+        }
+
+        let (mantissa, exp, sign) = decode_f64(value);
+        let inner = if mantissa == 0 {
+            BigUInt::from(0u64)
+        } else if exp >= 0 {
+            BigUInt::from(mantissa) << exp as u32
+        } else if i64::from(mantissa.trailing_zeros()) >= -exp {
+            BigUInt::from(mantissa >> -exp)
+        } else {
+            return Err(ConversionError::NotIntegral);
+        };
+        Ok(Self::from((inner, sign)).fix_zero())
+    }
+}
+
+impl TryFrom<f32> for BigInt {
+    type Error = ConversionError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::try_from(f64::from(value))
+    }
+}
+
+impl TryFrom<f64> for BigUInt {
+    type Error = ConversionError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::try_from(BigInt::try_from(value)?)
+    }
+}
+
+impl TryFrom<f32> for BigUInt {
+    type Error = ConversionError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::try_from(f64::from(value))
+    }
+}
+
+// the highest 64 bits with a sticky lowest bit that is set if any dropped bit is set,
+// and the number of dropped bits. The sticky bit lies below the precision of any float,
+// so rounding the result rounds the whole number correctly
+fn top_bits(value: &BigUInt) -> (u64, u64) {
+    let bits = value.bit_length();
+    if bits <= 64 {
+        return (value.0.first().copied().unwrap_or(0), 0);
+    }
+
+    let shift = bits - 64;
+    let top = to_u128(&(value >> shift as u32)).unwrap() as u64;
+    let sticky = value.trailing_zeros().is_some_and(|zeros| zeros < shift);
+    (top | u64::from(sticky), shift)
+}
+
+impl BigUInt {
+    // nearest float with ties to even, infinity if the value is too large
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        if self.bit_length() > f64::MAX_EXP as u64 {
+            return f64::INFINITY;
+        }
+        let (top, shift) = top_bits(self);
+        top as f64 * f64::from_bits((1023 + shift) << 52)
+    }
+
+    #[must_use]
+    pub fn to_f32(&self) -> f32 {
+        if self.bit_length() > f32::MAX_EXP as u64 {
+            return f32::INFINITY;
+        }
+        let (top, shift) = top_bits(self);
+        top as f32 * f32::from_bits((127 + shift as u32) << 23)
+    }
+
+    // little-endian bytes without leading zeros, [0] for zero
+    #[must_use]
+    pub fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        while bytes.len() > 1 && bytes.last() == Some(&0) {
+            bytes.pop();
         }
+        if bytes.is_empty() {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    #[must_use]
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    #[must_use]
+    pub fn from_bytes_le(bytes: &[u8]) -> Self {
+        Self::from(limbs_from_bytes_le(bytes, 0))
+    }
+
+    #[must_use]
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_bytes_le(&bytes)
+    }
+}
+
+// limbs of little-endian bytes, `fill` pads the last limb; leading zero limbs are removed
+fn limbs_from_bytes_le(bytes: &[u8], fill: u8) -> Vec<u64> {
+    let mut limbs: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut limb = [fill; 8];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(limb)
+        })
+        .collect();
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    limbs
+}
+
+impl BigInt {
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        let value = self.inner.to_f64();
+        match self.sign {
+            Sign::Plus => value,
+            Sign::Minus => -value,
+        }
+    }
+
+    #[must_use]
+    pub fn to_f32(&self) -> f32 {
+        let value = self.inner.to_f32();
+        match self.sign {
+            Sign::Plus => value,
+            Sign::Minus => -value,
+        }
+    }
+
+    // sign and little-endian bytes of the magnitude
+    #[must_use]
+    pub fn to_bytes_le(&self) -> (Sign, Vec<u8>) {
+        (self.sign, self.inner.to_bytes_le())
+    }
+
+    #[must_use]
+    pub fn to_bytes_be(&self) -> (Sign, Vec<u8>) {
+        (self.sign, self.inner.to_bytes_be())
+    }
+
+    #[must_use]
+    pub fn from_bytes_le(sign: Sign, bytes: &[u8]) -> Self {
+        Self::from((BigUInt::from_bytes_le(bytes), sign)).fix_zero()
+    }
+
+    #[must_use]
+    pub fn from_bytes_be(sign: Sign, bytes: &[u8]) -> Self {
+        Self::from((BigUInt::from_bytes_be(bytes), sign)).fix_zero()
+    }
+
+    // shortest little-endian two's complement, so 128 is [0x80, 0x00] and -128 is [0x80]
+    #[must_use]
+    pub fn to_signed_bytes_le(&self) -> Vec<u8> {
+        let limbs = crate::bits::to_twos_complement(self, self.inner.0.len() + 1);
+        let mut bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        // a byte is redundant if it only repeats the sign bit of the byte below it
+        while let [.., below, last] = bytes[..] {
+            let sign_byte = if below & 0x80 == 0 { 0 } else { 0xFF };
+            if last != sign_byte {
+                break;
+            }
+            bytes.pop();
+        }
+        bytes
+    }
+
+    #[must_use]
+    pub fn to_signed_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_bytes_le();
+        bytes.reverse();
+        bytes
+    }
+
+    // the highest bit of the last byte is the sign, empty input is zero
+    #[must_use]
+    pub fn from_signed_bytes_le(bytes: &[u8]) -> Self {
+        let negative = bytes.last().is_some_and(|byte| byte & 0x80 != 0);
+        if !negative {
+            return Self::from(BigUInt::from_bytes_le(bytes));
+        }
+        let mut limbs = limbs_from_bytes_le(bytes, 0xFF);
+        // sign extension for the carry of the negation
+        limbs.push(u64::MAX);
+        crate::bits::from_twos_complement(limbs, true)
+    }
+
+    #[must_use]
+    pub fn from_signed_bytes_be(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_signed_bytes_le(&bytes)
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParseIntError {
     Empty,
-    InvalidDigit,
+    // byte offset of the first invalid character in the parsed string
+    InvalidDigit { position: usize },
+}
+
+impl Display for ParseIntError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseIntError::InvalidDigit { position } => {
+                write!(f, "invalid digit found in string at position {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseIntError {}
+
+fn check_radix(radix: u32) {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must lie in the range [2, 36], got {radix}"
+    );
+}
+
+// parses unsigned digits, `offset` is the position of `s` in the original string
+pub(crate) fn parse_unsigned(s: &str, radix: u32, offset: usize) -> Result<BigUInt, ParseIntError> {
+    if s.is_empty() {
+        return Err(ParseIntError::Empty);
+    }
+
+    let digits = s
+        .char_indices()
+        .map(|(i, ch)| {
+            ch.to_digit(radix)
+                .map(|d| d as u8)
+                .ok_or(ParseIntError::InvalidDigit {
+                    position: offset + i,
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(crate::radix::parse_radix(&digits, radix))
+}
+
+pub(crate) fn parse_signed(s: &str, radix: u32, offset: usize) -> Result<BigInt, ParseIntError> {
+    if let Some(s) = s.strip_prefix('-') {
+        let inner = parse_unsigned(s, radix, offset + 1)?;
+        Ok(BigInt::from((inner, Sign::Minus)).fix_zero())
+    } else {
+        Ok(BigInt::from((
+            parse_unsigned(s, radix, offset)?,
+            Sign::Plus,
+        )))
+    }
+}
+
+impl BigUInt {
+    // panics if radix is not in the range [2, 36], like `u64::from_str_radix`
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+        check_radix(radix);
+        parse_unsigned(s, radix, 0)
+    }
+
+    // lowercase digits without prefix
+    #[must_use]
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        check_radix(radix);
+        crate::radix::format_radix(self, radix)
+    }
+}
+
+impl BigInt {
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+        check_radix(radix);
+        parse_signed(s, radix, 0)
+    }
+
+    #[must_use]
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.inner.to_str_radix(radix);
+        match self.sign {
+            Sign::Plus => digits,
+            Sign::Minus => format!("-{digits}"),
+        }
+    }
 }
 
 impl FromStr for BigUInt {
     type Err = ParseIntError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.is_empty() {
-            return Err(ParseIntError::Empty);
-        }
-
-        s.chars().try_fold(Self::from(0), |mut acc, ch| {
-            if ch.is_ascii_digit() {
-                let n = ch as u64 - u64::from(b'0');
-                acc *= 10;
-                acc += n;
-                Ok(acc)
-            } else {
-                Err(ParseIntError::InvalidDigit)
-            }
-        })
+        parse_unsigned(s, 10, 0)
     }
 }
 
 impl Display for BigUInt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_zero() {
-            write!(f, "0")
-        } else {
-            let mut buff = Vec::new();
-            let mut num = self.clone();
-
-            while !num.is_zero() {
-                let (div, rem) = num.div_rem(Self::from(10));
-                num = div;
-                let digit = u64::try_from(&rem).unwrap() as u32;
-                buff.push(char::from_digit(digit, 10).unwrap());
-            }
-
-            write!(f, "{}", buff.into_iter().rev().collect::<String>())
-        }
+        f.pad_integral(true, "", &self.to_str_radix(10))
     }
 }
 
@@ -137,51 +520,127 @@ impl FromStr for BigInt {
     type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some(s) = s.trim().strip_prefix('-') {
-            Ok(Self::from((s.parse()?, Sign::Minus)).fix_zero())
-        } else {
-            Ok(Self::from((s.parse()?, Sign::Plus)))
-        }
+        let offset = s.len() - s.trim_start().len();
+        parse_signed(s.trim(), 10, offset)
     }
 }
 
 impl Display for BigInt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let sign = match self.sign {
-            Sign::Plus => "",
-            Sign::Minus => "-",
-        };
+        f.pad_integral(self.sign == Sign::Plus, "", &self.inner.to_str_radix(10))
+    }
+}
 
-        write!(f, "{}", sign)?;
+// negative numbers are written as sign and magnitude, e.g. `-0xff`
+macro_rules! impl_radix_fmt {
+    ($trait:ident, $radix:expr, $prefix:expr, $uppercase:expr) => {
+        impl $trait for BigUInt {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let mut digits = self.to_str_radix($radix);
+                if $uppercase {
+                    digits.make_ascii_uppercase();
+                }
+                f.pad_integral(true, $prefix, &digits)
+            }
+        }
 
-        self.inner.fmt(f)
-    }
+        impl $trait for BigInt {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let mut digits = self.inner.to_str_radix($radix);
+                if $uppercase {
+                    digits.make_ascii_uppercase();
+                }
+                f.pad_integral(self.sign == Sign::Plus, $prefix, &digits)
+            }
+        }
+    };
 }
 
+impl_radix_fmt!(LowerHex, 16, "0x", false);
+impl_radix_fmt!(UpperHex, 16, "0x", true);
+impl_radix_fmt!(Octal, 8, "0o", false);
+impl_radix_fmt!(Binary, 2, "0b", false);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn biguint_from_u64_test() {
-        assert_eq!(BigUInt::from(0).0, vec![]);
-        assert_eq!(BigUInt::from(123_456_789).0, vec![123_456_789]);
+        assert_eq!(BigUInt::from(0u64).0, Vec::<u64>::new());
+        assert_eq!(BigUInt::from(123_456_789u64).0, vec![123_456_789]);
     }
 
     #[test]
     fn biguint_parse_test() {
-        assert_eq!("0".parse(), Ok(BigUInt::from(0)));
-        assert_eq!("123456789".parse(), Ok(BigUInt::from(123_456_789)));
+        assert_eq!("0".parse(), Ok(BigUInt::from(0u64)));
+        assert_eq!("123456789".parse(), Ok(BigUInt::from(123_456_789u64)));
         assert_eq!(
             "12345678910111213141516".parse(),
             Ok(BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]))
         );
+
+        assert_eq!("".parse::<BigUInt>(), Err(ParseIntError::Empty));
+        assert_eq!(
+            "12a45".parse::<BigUInt>(),
+            Err(ParseIntError::InvalidDigit { position: 2 })
+        );
+        assert_eq!(
+            "-1".parse::<BigUInt>(),
+            Err(ParseIntError::InvalidDigit { position: 0 })
+        );
+    }
+
+    #[test]
+    fn biguint_radix_test() {
+        let num = BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]);
+        assert_eq!(num.to_str_radix(16), "29d42b65689328bbe0c");
+        assert_eq!(
+            BigUInt::from_str_radix("29D42b65689328bbe0c", 16),
+            Ok(num.clone())
+        );
+        assert_eq!(
+            BigUInt::from_str_radix(&num.to_str_radix(36), 36),
+            Ok(num.clone())
+        );
+        assert_eq!(BigUInt::from(255u64).to_str_radix(2), "11111111");
+        assert_eq!(
+            BigUInt::from_str_radix("11111111", 2),
+            Ok(BigUInt::from(255u64))
+        );
+        assert_eq!(
+            BigUInt::from_str_radix("102", 2),
+            Err(ParseIntError::InvalidDigit { position: 2 })
+        );
+        assert_eq!(BigUInt::from_str_radix("", 16), Err(ParseIntError::Empty));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must lie in the range [2, 36]")]
+    fn biguint_invalid_radix_test() {
+        let _ = BigUInt::from_str_radix("1", 37);
+    }
+
+    #[test]
+    fn biguint_fmt_test() {
+        let num = BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]);
+        assert_eq!(format!("{num:x}"), "29d42b65689328bbe0c");
+        assert_eq!(format!("{num:X}"), "29D42B65689328BBE0C");
+        assert_eq!(format!("{num:#x}"), "0x29d42b65689328bbe0c");
+        assert_eq!(format!("{num:o}"), "2472412662550446242737014");
+        assert_eq!(format!("{:b}", BigUInt::from(5u64)), "101");
+        assert_eq!(format!("{:#010b}", BigUInt::from(5u64)), "0b00000101");
+        assert_eq!(format!("{:>6x}", BigUInt::from(255u64)), "    ff");
+        assert_eq!(format!("{:<6}|", BigUInt::from(255u64)), "255   |");
+        assert_eq!(format!("{:06}", BigUInt::from(255u64)), "000255");
+        assert_eq!(format!("{:+}", BigUInt::from(255u64)), "+255");
+        assert_eq!(format!("{:x}", BigUInt::from(0u64)), "0");
     }
 
     #[test]
     fn biguint_display_test() {
-        assert_eq!(BigUInt::from(0).to_string(), "0");
-        assert_eq!(BigUInt::from(123_456_789).to_string(), "123456789");
+        assert_eq!(BigUInt::from(0u64).to_string(), "0");
+        assert_eq!(BigUInt::from(123_456_789u64).to_string(), "123456789");
         assert_eq!(
             BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]).to_string(),
             "12345678910111213141516"
@@ -191,27 +650,36 @@ mod tests {
     #[test]
     fn bigint_from_u64_test() {
         let bigint = BigInt::from(0u64);
-        assert_eq!((bigint.inner, bigint.sign), (BigUInt::from(0), Sign::Plus));
+        assert_eq!(
+            (bigint.inner, bigint.sign),
+            (BigUInt::from(0u64), Sign::Plus)
+        );
 
         let bigint = BigInt::from(123_456_789u64);
         assert_eq!(
             (bigint.inner, bigint.sign),
-            (BigUInt::from(123_456_789), Sign::Plus)
+            (BigUInt::from(123_456_789u64), Sign::Plus)
         );
     }
 
     #[test]
     fn bigint_from_i64_test() {
         let bigint = BigInt::from(0i64);
-        assert_eq!((bigint.inner, bigint.sign), (BigUInt::from(0), Sign::Plus));
+        assert_eq!(
+            (bigint.inner, bigint.sign),
+            (BigUInt::from(0u64), Sign::Plus)
+        );
 
         let bigint = BigInt::from(1i64);
-        assert_eq!((bigint.inner, bigint.sign), (BigUInt::from(1), Sign::Plus));
+        assert_eq!(
+            (bigint.inner, bigint.sign),
+            (BigUInt::from(1u64), Sign::Plus)
+        );
 
         let bigint = BigInt::from(-123_456_789i64);
         assert_eq!(
             (bigint.inner, bigint.sign),
-            (BigUInt::from(123_456_789), Sign::Minus)
+            (BigUInt::from(123_456_789u64), Sign::Minus)
         );
     }
 
@@ -233,6 +701,30 @@ mod tests {
                 Sign::Minus
             )))
         );
+
+        assert_eq!(
+            "  -12x".parse::<BigInt>(),
+            Err(ParseIntError::InvalidDigit { position: 5 })
+        );
+        assert_eq!("-".parse::<BigInt>(), Err(ParseIntError::Empty));
+    }
+
+    #[test]
+    fn bigint_radix_test() {
+        assert_eq!(BigInt::from(-255).to_str_radix(16), "-ff");
+        assert_eq!(BigInt::from_str_radix("-ff", 16), Ok(BigInt::from(-255)));
+        assert_eq!(BigInt::from_str_radix("-0", 16), Ok(BigInt::from(0)));
+        assert_eq!(
+            BigInt::from_str_radix("-fg", 16),
+            Err(ParseIntError::InvalidDigit { position: 2 })
+        );
+
+        assert_eq!(format!("{:x}", BigInt::from(-255)), "-ff");
+        assert_eq!(format!("{:#X}", BigInt::from(-255)), "-0xFF");
+        assert_eq!(format!("{:#08x}", BigInt::from(-255)), "-0x000ff");
+        assert_eq!(format!("{:o}", BigInt::from(8)), "10");
+        assert_eq!(format!("{:>5}", BigInt::from(-12)), "  -12");
+        assert_eq!(format!("{:05}", BigInt::from(-12)), "-0012");
     }
 
     #[test]
@@ -249,4 +741,157 @@ mod tests {
             "-12345678910111213141516"
         );
     }
+
+    #[test]
+    fn primitive_test() {
+        assert_eq!(BigUInt::from(u128::MAX).0, vec![u64::MAX, u64::MAX]);
+        assert_eq!(BigUInt::from(0u128).0, Vec::<u64>::new());
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(BigInt::from(u8::MAX), BigInt::from(255));
+
+        assert_eq!(u128::try_from(BigUInt::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(u8::try_from(&BigUInt::from(255u64)), Ok(255));
+        assert_eq!(
+            u8::try_from(&BigUInt::from(256u64)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(
+            u64::try_from(BigUInt::from(vec![0, 1])),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(usize::try_from(BigUInt::from(7u64)), Ok(7));
+
+        for value in [i128::MIN, i128::MAX, 0, -1, i128::from(i64::MIN) - 1] {
+            assert_eq!(i128::try_from(BigInt::from(value)), Ok(value));
+        }
+        assert_eq!(i8::try_from(BigInt::from(-128)), Ok(-128));
+        assert_eq!(
+            i8::try_from(BigInt::from(-129)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(
+            i128::try_from(BigInt::from(u128::MAX)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(
+            i128::try_from(-BigInt::from(1u128 << 127) - BigInt::from(1)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(u128::try_from(BigInt::from(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(
+            u32::try_from(&BigInt::from(-1)),
+            Err(ConversionError::OutOfRange)
+        );
+        assert_eq!(isize::try_from(BigInt::from(-5)), Ok(-5));
+        assert_eq!(
+            BigUInt::try_from(BigInt::from(-5)),
+            Err(ConversionError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn to_float_test() {
+        // `u128 as f64` rounds correctly and serves as the reference
+        let mut x = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C834u128;
+        for _ in 0..1000 {
+            x = x.wrapping_mul(0x2545_F491_4F6C_DD1D).wrapping_add(1);
+            let value = x >> (x % 128);
+            assert_eq!(BigUInt::from(value).to_f64(), value as f64, "{value}");
+            assert_eq!(BigUInt::from(value).to_f32(), value as f32, "{value}");
+            let signed = -(value as i128 >> 1);
+            assert_eq!(BigInt::from(signed).to_f64(), signed as f64, "{signed}");
+        }
+
+        // ties to even, and a set bit far below breaks the tie
+        let two53 = BigUInt::from(1u64 << 53);
+        assert_eq!((two53.clone() + 1).to_f64(), 2f64.powi(53));
+        assert_eq!((two53.clone() + 3).to_f64(), 2f64.powi(53) + 4.0);
+        let tie = (two53.clone() + 1) << 200;
+        assert_eq!(tie.to_f64(), 2f64.powi(253));
+        assert_eq!((tie + 1).to_f64(), 2f64.powi(253) + 2f64.powi(201));
+
+        let max = BigUInt::from((1u64 << 53) - 1) << 971;
+        assert_eq!(max.to_f64(), f64::MAX);
+        assert_eq!((BigUInt::from(1u64) << 1024).to_f64(), f64::INFINITY);
+        assert_eq!(((BigUInt::from(1u64) << 1024) - 1).to_f64(), f64::INFINITY);
+        assert_eq!((BigUInt::from(1u64) << 127).to_f32(), 2f32.powi(127));
+        assert_eq!((BigUInt::from(1u64) << 128).to_f32(), f32::INFINITY);
+        assert_eq!(BigInt::from(0).to_f64(), 0.0);
+        assert_eq!((-(BigInt::from(1) << 2000)).to_f64(), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn from_float_test() {
+        assert_eq!(BigInt::try_from(-3.0), Ok(BigInt::from(-3)));
+        assert_eq!(BigInt::try_from(-0.0), Ok(BigInt::from(0)));
+        assert_eq!(
+            BigInt::try_from(1e300).unwrap().to_string(),
+            format!("{:.0}", 1e300)
+        );
+        assert_eq!(
+            BigInt::try_from(2f64.powi(60) + 2f64.powi(8)),
+            Ok(BigInt::from((1u64 << 60) + 256))
+        );
+        assert_eq!(BigInt::try_from(2.5), Err(ConversionError::NotIntegral));
+        assert_eq!(
+            BigInt::try_from(f64::from_bits(1)),
+            Err(ConversionError::NotIntegral)
+        );
+        assert_eq!(BigInt::try_from(f64::NAN), Err(ConversionError::NotFinite));
+        assert_eq!(
+            BigInt::try_from(f32::INFINITY),
+            Err(ConversionError::NotFinite)
+        );
+        assert_eq!(BigInt::try_from(16_777_216f32), Ok(BigInt::from(1 << 24)));
+        assert_eq!(BigUInt::try_from(7.0), Ok(BigUInt::from(7u64)));
+        assert_eq!(BigUInt::try_from(-7.0), Err(ConversionError::OutOfRange));
+    }
+
+    #[test]
+    fn bytes_test() {
+        let value = BigUInt::from(0x0102_0304_0506_0708_090Au128);
+        let le = [0x0A, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+        assert_eq!(value.to_bytes_le(), le);
+        assert_eq!(value.to_bytes_be(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 0x0A]);
+        assert_eq!(BigUInt::from_bytes_le(&le), value);
+        assert_eq!(BigUInt::from_bytes_be(&value.to_bytes_be()), value);
+        assert_eq!(
+            BigUInt::from_bytes_le(&[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            BigUInt::from(1u64)
+        );
+        assert_eq!(BigUInt::from(0u64).to_bytes_le(), [0]);
+        assert_eq!(BigUInt::from_bytes_be(&[]), BigUInt::from(0u64));
+
+        let negative = BigInt::from(-0x0102);
+        assert_eq!(negative.to_bytes_be(), (Sign::Minus, vec![1, 2]));
+        assert_eq!(BigInt::from_bytes_le(Sign::Minus, &[2, 1]), negative);
+        assert_eq!(BigInt::from_bytes_le(Sign::Minus, &[0]), BigInt::from(0));
+
+        for (value, expected) in [
+            (0, vec![0]),
+            (-1, vec![0xFF]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0]),
+            (-128, vec![0x80]),
+            (-129, vec![0x7F, 0xFF]),
+            (i128::from(i64::MIN), vec![0, 0, 0, 0, 0, 0, 0, 0x80]),
+            (-(1 << 64), vec![0, 0, 0, 0, 0, 0, 0, 0, 0xFF]),
+            (
+                u64::MAX as i128,
+                vec![0xFF; 8].into_iter().chain([0]).collect(),
+            ),
+        ] {
+            let big = BigInt::from(value);
+            assert_eq!(big.to_signed_bytes_le(), expected, "{value}");
+            assert_eq!(BigInt::from_signed_bytes_le(&expected), big, "{value}");
+            // the full-width primitive representation is read the same way
+            assert_eq!(BigInt::from_signed_bytes_le(&value.to_le_bytes()), big);
+            assert_eq!(BigInt::from_signed_bytes_be(&value.to_be_bytes()), big);
+            let mut be = expected.clone();
+            be.reverse();
+            assert_eq!(big.to_signed_bytes_be(), be, "{value}");
+        }
+        assert_eq!(BigInt::from_signed_bytes_le(&[]), BigInt::from(0));
+        assert_eq!(BigInt::from_signed_bytes_be(&[0xFF; 20]), BigInt::from(-1));
+    }
 }
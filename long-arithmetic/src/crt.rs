@@ -0,0 +1,400 @@
+use super::rational::BigRational;
+use super::{BigInt, BigUInt, Sign};
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+// (a * b + c) mod m, the intermediate result fits into u128
+fn mul_add_mod(a: u64, b: u64, c: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128 + c as u128) % m as u128) as u64
+}
+
+// (a - b) mod m for a, b < m
+fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+// a^(-1) mod m by the extended Euclidean algorithm, if gcd(a, m) = 1
+fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (i128::from(m), i128::from(a % m));
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(i128::from(m)) as u64)
+}
+
+// The smallest non-negative x with x = residues[i] (mod moduli[i]) for all i, or `None`
+// if the congruences contradict each other. The moduli don't have to be coprime, the
+// solution is unique modulo their lcm. Residues may be negative, moduli must be positive
+#[must_use]
+pub fn crt(residues: &[BigInt], moduli: &[BigInt]) -> Option<BigInt> {
+    assert_eq!(
+        residues.len(),
+        moduli.len(),
+        "every residue needs a modulus"
+    );
+
+    // x is the solution modulo m for the congruences so far
+    let mut x = BigInt::from(0);
+    let mut m = BigInt::from(1);
+    for (a, n) in residues.iter().zip(moduli) {
+        assert!(
+            n.sign == Sign::Plus && !n.inner.is_zero(),
+            "moduli must be positive"
+        );
+
+        // x + m * t = a (mod n) is solvable iff g = gcd(m, n) divides a - x
+        let g = m.gcd(n);
+        let (diff, rem) = (a - &x).div_rem_ref(&g);
+        if !rem.inner.is_zero() {
+            return None;
+        }
+
+        let n_g = n / &g;
+        if n_g.inner != 1u64 {
+            let inv = (&m / &g).modinv(&n_g).expect("m / g and n / g are coprime");
+            let t = (&diff * &inv).rem_euclid(&n_g);
+            x += &m * &t;
+        }
+        m *= &n_g;
+    }
+    Some(x)
+}
+
+// The fraction n / d with |n|, d <= sqrt((modulus - 1) / 2) and n = value * d (mod modulus).
+// It is unique if it exists, so a rational number with a small enough numerator and
+// denominator is recovered from its residue (Wang's algorithm)
+#[must_use]
+pub fn rational_reconstruction(value: &BigUInt, modulus: &BigUInt) -> Option<BigRational> {
+    let bound = BigInt::from(((modulus.clone() - 1) >> 1u32).sqrt());
+
+    // the remainders and the cofactors of `value` of the Euclidean algorithm
+    let (mut r0, mut r1) = (BigInt::from(modulus.clone()), BigInt::from(value % modulus));
+    let (mut t0, mut t1) = (BigInt::from(0), BigInt::from(1));
+    while r1 > bound {
+        let (q, r) = r0.div_rem_ref(&r1);
+        (r0, r1) = (r1, r);
+        let t = &t0 - &(&q * &t1);
+        (t0, t1) = (t1, t);
+    }
+
+    if t1.inner > bound.inner || r1.inner.gcd(&t1.inner) != 1u64 {
+        return None;
+    }
+    Some(BigRational::new(r1, t1))
+}
+
+// Reconstruction of numbers from their residues modulo pairwise coprime 64-bit moduli,
+// typically primes, so that a computation on big integers can be carried out as many
+// independent computations in machine words. Garner's algorithm converts the residues
+// into the mixed radix representation x = v0 + v1 * m0 + v2 * m0 * m1 + ...
+#[derive(Clone, Debug)]
+pub struct MultiModular {
+    moduli: Vec<u64>,
+    // (m0 * ... * m(i-1))^(-1) mod m(i)
+    inverses: Vec<u64>,
+    product: BigUInt,
+}
+
+impl MultiModular {
+    #[must_use]
+    pub fn new(moduli: Vec<u64>) -> Self {
+        let inverses = (0..moduli.len())
+            .map(|i| {
+                let m = moduli[i];
+                assert!(m > 1, "moduli must be greater than 1");
+                let prefix = moduli[..i].iter().fold(1 % m, |acc, &p| mul_mod(acc, p, m));
+                inv_mod(prefix, m).expect("moduli must be pairwise coprime")
+            })
+            .collect();
+
+        let mut product = BigUInt::from(1u64);
+        for &m in &moduli {
+            product *= m;
+        }
+
+        Self {
+            moduli,
+            inverses,
+            product,
+        }
+    }
+
+    // the largest primes below 2^64 that are enough to reconstruct signed numbers
+    // of up to `bits` bits, i.e. with a product of at least 2^(bits + 1)
+    #[must_use]
+    pub fn with_bits(bits: u64) -> Self {
+        let mut primes = vec![];
+        let mut product_bits = 0.0;
+        let mut candidate = u64::MAX;
+        while product_bits < (bits + 1) as f64 {
+            if BigUInt::from(candidate).is_probable_prime(0) {
+                primes.push(candidate);
+                // rounded down, every prime is more than 2^63
+                product_bits += 63.0;
+            }
+            candidate -= 2;
+        }
+        Self::new(primes)
+    }
+
+    #[must_use]
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    // product of the moduli, the range of the reconstruction
+    #[must_use]
+    pub fn product(&self) -> &BigUInt {
+        &self.product
+    }
+
+    // `value` modulo each modulus, negative numbers are reduced to non-negative residues
+    #[must_use]
+    pub fn residues(&self, value: &BigInt) -> Vec<u64> {
+        self.moduli
+            .iter()
+            .map(|&m| {
+                let r = crate::div::rem_limb(&value.inner.0, m);
+                match value.sign {
+                    Sign::Minus if r != 0 => m - r,
+                    _ => r,
+                }
+            })
+            .collect()
+    }
+
+    // the number in [0, product) with the given residues
+    #[must_use]
+    pub fn reconstruct(&self, residues: &[u64]) -> BigUInt {
+        assert_eq!(
+            residues.len(),
+            self.moduli.len(),
+            "every modulus needs a residue"
+        );
+
+        // mixed radix digits, v(i) = (r(i) - (v0 + v1 * m0 + ...)) / (m0 * ... * m(i-1)) mod m(i)
+        let mut digits: Vec<u64> = Vec::with_capacity(residues.len());
+        for (i, (&r, &m)) in residues.iter().zip(&self.moduli).enumerate() {
+            let lower = (0..i)
+                .rev()
+                .fold(0, |acc, j| mul_add_mod(acc, self.moduli[j], digits[j], m));
+            let diff = sub_mod(r % m, lower, m);
+            digits.push(mul_mod(diff, self.inverses[i], m));
+        }
+
+        let mut value = BigUInt::from(0u64);
+        for (&digit, &m) in digits.iter().zip(&self.moduli).rev() {
+            value *= m;
+            value += digit;
+        }
+        value
+    }
+
+    // the number in (-product / 2, product / 2] with the given residues
+    #[must_use]
+    pub fn reconstruct_signed(&self, residues: &[u64]) -> BigInt {
+        let value = self.reconstruct(residues);
+        if (value.clone() << 1u32) > self.product {
+            -BigInt::from(&self.product - &value)
+        } else {
+            BigInt::from(value)
+        }
+    }
+
+    // the fraction with the given residues, see `rational_reconstruction`
+    #[must_use]
+    pub fn reconstruct_rational(&self, residues: &[u64]) -> Option<BigRational> {
+        rational_reconstruction(&self.reconstruct(residues), &self.product)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pow;
+
+    fn int(n: i64) -> BigInt {
+        BigInt::from(n)
+    }
+
+    fn ints(values: &[i64]) -> Vec<BigInt> {
+        values.iter().map(|&n| int(n)).collect()
+    }
+
+    #[test]
+    fn inv_mod_test() {
+        assert_eq!(inv_mod(3, 7), Some(5));
+        assert_eq!(inv_mod(4, 8), None);
+        assert_eq!(inv_mod(5, 1), Some(0));
+        let p = u64::MAX - 58;
+        let inv = inv_mod(12345, p).unwrap();
+        assert_eq!(mul_mod(inv, 12345, p), 1);
+    }
+
+    #[test]
+    fn crt_test() {
+        // coprime moduli
+        assert_eq!(crt(&ints(&[2, 3, 2]), &ints(&[3, 5, 7])), Some(int(23)));
+        assert_eq!(crt(&ints(&[-1, -1]), &ints(&[4, 9])), Some(int(35)));
+        assert_eq!(crt(&[], &[]), Some(int(0)));
+
+        // common factors
+        assert_eq!(crt(&ints(&[3, 5]), &ints(&[4, 6])), Some(int(11)));
+        assert_eq!(crt(&ints(&[3, 4]), &ints(&[4, 6])), None);
+        assert_eq!(crt(&ints(&[5, 5, 5]), &ints(&[6, 6, 9])), Some(int(5)));
+        assert_eq!(crt(&ints(&[1, 0]), &ints(&[3, 1])), Some(int(1)));
+
+        // large moduli against a known value
+        let x = (BigInt::from(1) << 300u32) - int(12345);
+        let moduli = [
+            (BigInt::from(1) << 127u32) - int(1),
+            (BigInt::from(1) << 89u32) - int(1),
+            int(1_000_000_007 * 6),
+            BigInt::from(u64::MAX) * int(6),
+        ];
+        let residues: Vec<BigInt> = moduli.iter().map(|m| x.rem_euclid(m)).collect();
+        let lcm = moduli.iter().fold(int(1), |acc, m| acc.lcm(m));
+        assert_eq!(crt(&residues, &moduli), Some(x.rem_euclid(&lcm)));
+    }
+
+    #[test]
+    fn rational_reconstruction_test() {
+        let p = BigUInt::from(1_000_000_007u64);
+        for (numer, denom) in [(22, 7), (-3, 4), (0, 1), (5, 1), (-100, 99)] {
+            let expected = BigRational::new(int(numer), int(denom));
+            let d_inv = BigUInt::from(denom as u64).modinv(&p).unwrap();
+            let n = int(numer).rem_euclid(&BigInt::from(p.clone())).inner;
+            let value = (&n * &d_inv) % &p;
+            assert_eq!(rational_reconstruction(&value, &p), Some(expected));
+        }
+
+        // 2^-1 mod 7 = 4, but both 1/2 and 4 are out of the bound sqrt(3)
+        let seven = BigUInt::from(7u64);
+        assert_eq!(rational_reconstruction(&BigUInt::from(4u64), &seven), None);
+        assert_eq!(
+            rational_reconstruction(&BigUInt::from(1u64), &seven),
+            Some(BigRational::from(int(1)))
+        );
+    }
+
+    #[test]
+    fn multi_modular_test() {
+        let mm = MultiModular::new(vec![3, 5, 7]);
+        assert_eq!(mm.product(), &BigUInt::from(105u64));
+        assert_eq!(mm.residues(&int(-1)), vec![2, 4, 6]);
+        assert_eq!(mm.reconstruct(&[2, 3, 2]), BigUInt::from(23u64));
+        assert_eq!(mm.reconstruct_signed(&[2, 4, 6]), int(-1));
+        assert_eq!(mm.reconstruct_signed(&mm.residues(&int(52))), int(52));
+        assert_eq!(mm.reconstruct_signed(&mm.residues(&int(-52))), int(-52));
+
+        let mm = MultiModular::with_bits(1000);
+        assert_eq!(mm.moduli()[0], u64::MAX - 58);
+        assert!(mm.product().bits_num() > 1001);
+        for x in [
+            (BigInt::from(1) << 1000u32) - int(1),
+            -(BigInt::from(3).pow(600)),
+            int(0),
+        ] {
+            assert_eq!(mm.reconstruct_signed(&mm.residues(&x)), x);
+        }
+
+        let x = BigRational::new(BigInt::from(7).pow(100), -(BigInt::from(11).pow(90)));
+        let residues: Vec<u64> = mm
+            .moduli()
+            .iter()
+            .zip(mm.residues(x.numer()))
+            .map(|(&m, n)| {
+                let d = (x.denom().clone() % m).0.first().copied().unwrap_or(0);
+                mul_mod(n, inv_mod(d, m).unwrap(), m)
+            })
+            .collect();
+        assert_eq!(mm.reconstruct_rational(&residues), Some(x));
+    }
+
+    // Solution of a linear system modulo p by Gaussian elimination, with the determinant.
+    // The solution times the determinant is an integer vector by Cramer's rule
+    fn solve_mod(matrix: &[Vec<i64>], rhs: &[i64], p: u64) -> (u64, Vec<u64>) {
+        let n = matrix.len();
+        let reduce = |x: i64| i128::from(x).rem_euclid(i128::from(p)) as u64;
+        let mut rows: Vec<Vec<u64>> = matrix
+            .iter()
+            .zip(rhs)
+            .map(|(row, &b)| row.iter().chain([&b]).map(|&x| reduce(x)).collect())
+            .collect();
+
+        let mut det = 1;
+        for col in 0..n {
+            let pivot = (col..n).find(|&r| rows[r][col] != 0).expect("singular");
+            if pivot != col {
+                rows.swap(pivot, col);
+                det = p - det;
+            }
+            det = mul_mod(det, rows[col][col], p);
+            let inv = inv_mod(rows[col][col], p).unwrap();
+            for x in &mut rows[col] {
+                *x = mul_mod(*x, inv, p);
+            }
+            let pivot_row = rows[col].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r == col {
+                    continue;
+                }
+                let factor = row[col];
+                for (x, &y) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *x = sub_mod(*x, mul_mod(factor, y, p), p);
+                }
+            }
+        }
+        (det, rows.iter().map(|row| row[n]).collect())
+    }
+
+    #[test]
+    fn linear_algebra_test() {
+        // a Hilbert-like matrix with a large determinant and fractional solution
+        let n = 8;
+        let matrix: Vec<Vec<i64>> = (0..n)
+            .map(|i| (0..n).map(|j| 1000 / (i + j + 1) + (i * j) % 7).collect())
+            .collect();
+        let rhs: Vec<i64> = (0..n).map(|i| i * i - 3).collect();
+
+        let mm = MultiModular::with_bits(300);
+        let solutions: Vec<(u64, Vec<u64>)> = mm
+            .moduli()
+            .iter()
+            .map(|&p| solve_mod(&matrix, &rhs, p))
+            .collect();
+
+        let det_residues: Vec<u64> = solutions.iter().map(|(det, _)| *det).collect();
+        let det = mm.reconstruct_signed(&det_residues);
+        let x: Vec<BigRational> = (0..n as usize)
+            .map(|i| {
+                let residues: Vec<u64> = solutions.iter().map(|(_, x)| x[i]).collect();
+                mm.reconstruct_rational(&residues).unwrap()
+            })
+            .collect();
+
+        // A * x = b exactly, and the denominators divide the determinant
+        for (row, &b) in matrix.iter().zip(&rhs) {
+            let sum = row
+                .iter()
+                .zip(&x)
+                .fold(BigRational::from(int(0)), |acc, (&a, x)| {
+                    &acc + &(&BigRational::from(int(a)) * x)
+                });
+            assert_eq!(sum, BigRational::from(int(b)));
+        }
+        for x in &x {
+            assert!((&det.inner % x.denom()).is_zero());
+        }
+        assert!(x.iter().any(|x| !x.is_integer()));
+    }
+}
@@ -0,0 +1,509 @@
+use std::hint::black_box;
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::helpers::trim_slice;
+use crate::ConversionError;
+
+use super::limbs::Limbs;
+use super::BigUInt;
+
+// bits of the exponent processed per multiplication in `ConstMontgomery::modpow`
+const WINDOW: usize = 4;
+
+// Constant-time arithmetic for secret values such as cryptographic keys. `ConstUInt`
+// has a fixed number of limbs and its operations run the same instructions and
+// memory accesses whatever the values are: there are no data-dependent branches,
+// early exits or trimmed limbs. Results of comparisons are `Choice` masks that
+// select values without branching. Only the widths and the modulus are public.
+// This is best effort, the compiler gives no guarantees about the generated code.
+
+// a secret boolean, all bits set for true
+#[derive(Clone, Copy, Debug)]
+pub struct Choice(u64);
+
+impl Choice {
+    pub const TRUE: Self = Self(u64::MAX);
+    pub const FALSE: Self = Self(0);
+
+    // `bit` must be 0 or 1, `black_box` keeps the optimizer from turning masks into branches
+    #[must_use]
+    pub fn from_bit(bit: u64) -> Self {
+        Self(black_box(bit).wrapping_neg())
+    }
+
+    #[must_use]
+    pub fn mask(self) -> u64 {
+        self.0
+    }
+}
+
+// reveals the value, use only when it may become public
+impl From<Choice> for bool {
+    fn from(choice: Choice) -> bool {
+        choice.0 != 0
+    }
+}
+
+impl Not for Choice {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+fn is_zero_u64(value: u64) -> Choice {
+    Choice::from_bit(((value | value.wrapping_neg()) >> 63) ^ 1)
+}
+
+// acc + a * b + carry as (low, high), never overflows
+fn mac(acc: u64, a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let wide = u128::from(a) * u128::from(b) + u128::from(acc) + u128::from(carry);
+    (wide as u64, (wide >> 64) as u64)
+}
+
+// Unsigned number of exactly `N` little-endian limbs, arithmetic wraps modulo 2^(64 * N).
+// Equality is constant-time, there is no `Ord` because ordering would branch on the value
+#[derive(Clone, Copy, Debug)]
+pub struct ConstUInt<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> ConstUInt<N> {
+    pub const ZERO: Self = Self([0; N]);
+    pub const ONE: Self = Self::from_u64(1);
+    pub const MAX: Self = Self([u64::MAX; N]);
+    pub const BITS: u32 = 64 * N as u32;
+
+    #[must_use]
+    pub const fn from_u64(value: u64) -> Self {
+        let mut limbs = [0; N];
+        limbs[0] = value;
+        Self(limbs)
+    }
+
+    // sum and whether it overflowed
+    #[must_use]
+    pub fn overflowing_add(&self, rhs: &Self) -> (Self, Choice) {
+        let mut result = [0; N];
+        let mut carry = false;
+        for ((r, &a), &b) in result.iter_mut().zip(&self.0).zip(&rhs.0) {
+            (*r, carry) = a.carrying_add(b, carry);
+        }
+        (Self(result), Choice::from_bit(u64::from(carry)))
+    }
+
+    #[must_use]
+    pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    // difference and whether it borrowed, that is `self < rhs`
+    #[must_use]
+    pub fn overflowing_sub(&self, rhs: &Self) -> (Self, Choice) {
+        let mut result = [0; N];
+        let mut borrow = false;
+        for ((r, &a), &b) in result.iter_mut().zip(&self.0).zip(&rhs.0) {
+            (*r, borrow) = a.borrowing_sub(b, borrow);
+        }
+        (Self(result), Choice::from_bit(u64::from(borrow)))
+    }
+
+    #[must_use]
+    pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    // full product as (low, high) halves
+    #[must_use]
+    pub fn widening_mul(&self, rhs: &Self) -> (Self, Self) {
+        let (mut low, mut high) = ([0; N], [0; N]);
+        for (i, &a) in self.0.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in rhs.0.iter().enumerate() {
+                // the position depends only on the indices
+                let limb = if i + j < N {
+                    &mut low[i + j]
+                } else {
+                    &mut high[i + j - N]
+                };
+                (*limb, carry) = mac(*limb, a, b, carry);
+            }
+            high[i] = carry;
+        }
+        (Self(low), Self(high))
+    }
+
+    #[must_use]
+    pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+        self.widening_mul(rhs).0
+    }
+
+    // `b` if `choice` is true, `a` otherwise
+    #[must_use]
+    pub fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut result = a.0;
+        for (r, &b) in result.iter_mut().zip(&b.0) {
+            *r ^= (*r ^ b) & choice.mask();
+        }
+        Self(result)
+    }
+
+    // exchanges the values if `choice` is true
+    pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        for (a, b) in a.0.iter_mut().zip(&mut b.0) {
+            let diff = (*a ^ *b) & choice.mask();
+            *a ^= diff;
+            *b ^= diff;
+        }
+    }
+
+    #[must_use]
+    pub fn ct_is_zero(&self) -> Choice {
+        is_zero_u64(self.0.iter().fold(0, |acc, &limb| acc | limb))
+    }
+
+    #[must_use]
+    pub fn ct_eq(&self, rhs: &Self) -> Choice {
+        let diff = self
+            .0
+            .iter()
+            .zip(&rhs.0)
+            .fold(0, |acc, (&a, &b)| acc | (a ^ b));
+        is_zero_u64(diff)
+    }
+
+    #[must_use]
+    pub fn ct_lt(&self, rhs: &Self) -> Choice {
+        self.overflowing_sub(rhs).1
+    }
+
+    #[must_use]
+    pub fn ct_gt(&self, rhs: &Self) -> Choice {
+        rhs.ct_lt(self)
+    }
+}
+
+impl<const N: usize> Default for ConstUInt<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> PartialEq for ConstUInt<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const N: usize> Eq for ConstUInt<N> {}
+
+// the conversions to and from `BigUInt` are not constant-time
+impl<const N: usize> From<&ConstUInt<N>> for BigUInt {
+    fn from(value: &ConstUInt<N>) -> Self {
+        BigUInt(Limbs::from(trim_slice(&value.0)))
+    }
+}
+
+impl<const N: usize> From<ConstUInt<N>> for BigUInt {
+    fn from(value: ConstUInt<N>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<const N: usize> TryFrom<&BigUInt> for ConstUInt<N> {
+    type Error = ConversionError;
+
+    fn try_from(value: &BigUInt) -> Result<Self, Self::Error> {
+        if value.0.len() > N {
+            return Err(ConversionError::OutOfRange);
+        }
+        let mut limbs = [0; N];
+        limbs[..value.0.len()].copy_from_slice(&value.0);
+        Ok(Self(limbs))
+    }
+}
+
+impl<const N: usize> TryFrom<BigUInt> for ConstUInt<N> {
+    type Error = ConversionError;
+
+    fn try_from(value: BigUInt) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+// Constant-time counterpart of `modular::MontgomeryContext` for a public odd modulus `n`
+// with R = 2^(64 * N). Values in Montgomery form `a * R mod n` are reduced, below `n`
+#[derive(Clone, Copy, Debug)]
+pub struct ConstMontgomery<const N: usize> {
+    modulus: ConstUInt<N>,
+    // -n^(-1) mod 2^64
+    n_inv: u64,
+    // R mod n, the Montgomery form of one
+    one: ConstUInt<N>,
+    // R^2 mod n
+    r2: ConstUInt<N>,
+}
+
+impl<const N: usize> ConstMontgomery<N> {
+    #[must_use]
+    pub fn new(modulus: ConstUInt<N>) -> Self {
+        assert!(
+            !modulus.0[0].is_multiple_of(2),
+            "Montgomery reduction requires an odd modulus"
+        );
+
+        // Newton iteration doubles the number of correct bits: 1 -> 2 -> ... -> 64
+        let n0 = modulus.0[0];
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(n0.wrapping_mul(inv)));
+        }
+
+        // the modulus is public, so the setup may use variable-time arithmetic
+        let n = BigUInt::from(&modulus);
+        let r = BigUInt::ONE << ConstUInt::<N>::BITS;
+        let reduced = |value: BigUInt| ConstUInt::try_from(value % &n).unwrap();
+
+        Self {
+            modulus,
+            n_inv: inv.wrapping_neg(),
+            one: reduced(r.clone()),
+            r2: reduced(r.square()),
+        }
+    }
+
+    #[must_use]
+    pub fn modulus(&self) -> &ConstUInt<N> {
+        &self.modulus
+    }
+
+    // lhs * rhs * R^(-1) mod n for lhs * rhs < n * R, interleaving the product with the
+    // reduction (CIOS). The intermediate value stays below 2n and needs N + 1 limbs
+    #[must_use]
+    pub fn mul(&self, lhs: &ConstUInt<N>, rhs: &ConstUInt<N>) -> ConstUInt<N> {
+        let n = &self.modulus.0;
+        let mut t = [0; N];
+        let mut top = 0u64;
+
+        for &b in &rhs.0 {
+            let mut carry = 0;
+            for (t, &a) in t.iter_mut().zip(&lhs.0) {
+                (*t, carry) = mac(*t, a, b, carry);
+            }
+            let (t_n, overflow) = top.overflowing_add(carry);
+
+            // adds a multiple of n that makes the lowest limb zero and drops it
+            let m = t[0].wrapping_mul(self.n_inv);
+            let (_, mut carry) = mac(t[0], m, n[0], 0);
+            for j in 1..N {
+                (t[j - 1], carry) = mac(t[j], m, n[j], carry);
+            }
+            let (high, carry) = t_n.overflowing_add(carry);
+            t[N - 1] = high;
+            top = u64::from(overflow) + u64::from(carry);
+        }
+
+        let t = ConstUInt(t);
+        let (reduced, borrow) = t.overflowing_sub(&self.modulus);
+        ConstUInt::conditional_select(&t, &reduced, Choice::from_bit(top) | !borrow)
+    }
+
+    // value * R mod n for any value, not only reduced ones
+    #[must_use]
+    pub fn to_montgomery(&self, value: &ConstUInt<N>) -> ConstUInt<N> {
+        self.mul(value, &self.r2)
+    }
+
+    #[must_use]
+    pub fn from_montgomery(&self, value: &ConstUInt<N>) -> ConstUInt<N> {
+        self.mul(value, &ConstUInt::ONE)
+    }
+
+    // (lhs + rhs) mod n for reduced operands, in either form
+    #[must_use]
+    pub fn add(&self, lhs: &ConstUInt<N>, rhs: &ConstUInt<N>) -> ConstUInt<N> {
+        let (sum, carry) = lhs.overflowing_add(rhs);
+        let (reduced, borrow) = sum.overflowing_sub(&self.modulus);
+        ConstUInt::conditional_select(&sum, &reduced, carry | !borrow)
+    }
+
+    // (lhs - rhs) mod n for reduced operands, in either form
+    #[must_use]
+    pub fn sub(&self, lhs: &ConstUInt<N>, rhs: &ConstUInt<N>) -> ConstUInt<N> {
+        let (diff, borrow) = lhs.overflowing_sub(rhs);
+        let wrapped = diff.wrapping_add(&self.modulus);
+        ConstUInt::conditional_select(&diff, &wrapped, borrow)
+    }
+
+    // lhs * rhs mod n for operands in the ordinary form
+    #[must_use]
+    pub fn modmul(&self, lhs: &ConstUInt<N>, rhs: &ConstUInt<N>) -> ConstUInt<N> {
+        self.mul(&self.to_montgomery(lhs), rhs)
+    }
+
+    // base^exp mod n in the ordinary form. Every window of the exponent costs the same
+    // squarings and one multiplication, the table entry is picked by scanning all of them
+    #[must_use]
+    pub fn modpow<const M: usize>(&self, base: &ConstUInt<N>, exp: &ConstUInt<M>) -> ConstUInt<N> {
+        let base = self.to_montgomery(base);
+        let mut table = [self.one; 1 << WINDOW];
+        for i in 1..table.len() {
+            table[i] = self.mul(&table[i - 1], &base);
+        }
+
+        let mut acc = self.one;
+        let windows_per_limb = 64 / WINDOW;
+        for window in (0..M * windows_per_limb).rev() {
+            for _ in 0..WINDOW {
+                acc = self.mul(&acc, &acc);
+            }
+
+            let shift = window % windows_per_limb * WINDOW;
+            let digit = exp.0[window / windows_per_limb] >> shift & ((1 << WINDOW) - 1);
+            let mut factor = self.one;
+            for (i, entry) in table.iter().enumerate() {
+                factor =
+                    ConstUInt::conditional_select(&factor, entry, is_zero_u64(digit ^ i as u64));
+            }
+            acc = self.mul(&acc, &factor);
+        }
+        self.from_montgomery(&acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limbs<const N: usize>(seed: u64) -> ConstUInt<N> {
+        ConstUInt(crate::test_util::limbs(seed, N).try_into().unwrap())
+    }
+
+    #[test]
+    fn choice_test() {
+        assert!(bool::from(Choice::TRUE) && !bool::from(Choice::FALSE));
+        assert!(bool::from(Choice::from_bit(1) & !Choice::from_bit(0)));
+        assert!(!bool::from(Choice::FALSE | Choice::from_bit(0)));
+        assert_eq!(Choice::from_bit(1).mask(), u64::MAX);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let modulus = BigUInt::ONE << ConstUInt::<4>::BITS;
+        for seed in 1..20 {
+            let (a, b) = (limbs::<4>(seed), limbs::<4>(seed * 1_000_003));
+            let (big_a, big_b) = (BigUInt::from(a), BigUInt::from(b));
+
+            let (sum, carry) = a.overflowing_add(&b);
+            assert_eq!(BigUInt::from(sum), (&big_a + &big_b) % &modulus);
+            assert_eq!(bool::from(carry), &big_a + &big_b >= modulus);
+
+            let (diff, borrow) = a.overflowing_sub(&b);
+            assert_eq!(bool::from(borrow), big_a < big_b);
+            assert_eq!(bool::from(a.ct_lt(&b)), big_a < big_b);
+            assert_eq!(bool::from(a.ct_gt(&b)), big_a > big_b);
+            assert_eq!(diff.wrapping_add(&b), a);
+
+            let (low, high) = a.widening_mul(&b);
+            let product = &big_a * &big_b;
+            assert_eq!(BigUInt::from(low), &product % &modulus);
+            assert_eq!(BigUInt::from(high), product >> ConstUInt::<4>::BITS);
+        }
+
+        assert!(bool::from(ConstUInt::<3>::ZERO.ct_is_zero()));
+        assert!(!bool::from(ConstUInt::<3>::ONE.ct_is_zero()));
+        assert_eq!(
+            ConstUInt::<2>::MAX.wrapping_add(&ConstUInt::ONE),
+            ConstUInt::ZERO
+        );
+        assert_eq!(
+            ConstUInt::<2>::ZERO.wrapping_sub(&ConstUInt::ONE),
+            ConstUInt::MAX
+        );
+        assert!(bool::from(ConstUInt::<2>([5, 7]).ct_eq(&ConstUInt([5, 7]))));
+        assert!(!bool::from(
+            ConstUInt::<2>([5, 7]).ct_eq(&ConstUInt([5, 8]))
+        ));
+    }
+
+    #[test]
+    fn select_test() {
+        let (mut a, mut b) = (limbs::<3>(1), limbs::<3>(2));
+        let (x, y) = (a, b);
+        assert_eq!(ConstUInt::conditional_select(&a, &b, Choice::FALSE), a);
+        assert_eq!(ConstUInt::conditional_select(&a, &b, Choice::TRUE), b);
+
+        ConstUInt::conditional_swap(&mut a, &mut b, Choice::FALSE);
+        assert_eq!((a, b), (x, y));
+        ConstUInt::conditional_swap(&mut a, &mut b, Choice::TRUE);
+        assert_eq!((a, b), (y, x));
+    }
+
+    #[test]
+    fn conversion_test() {
+        let value = BigUInt::from(vec![1, 2, 3]);
+        let fixed = ConstUInt::<4>::try_from(&value).unwrap();
+        assert_eq!(fixed, ConstUInt([1, 2, 3, 0]));
+        assert_eq!(BigUInt::from(fixed), value);
+        assert_eq!(BigUInt::from(ConstUInt::<4>::ZERO), BigUInt::ZERO);
+        assert_eq!(
+            ConstUInt::<2>::try_from(value),
+            Err(ConversionError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn montgomery_test() {
+        // odd moduli, including ones with the top bit set and a small one in a wide type
+        let mut moduli = vec![ConstUInt::<4>::MAX, ConstUInt::from_u64(1_000_000_007)];
+        for seed in 1..6 {
+            let mut n = limbs::<4>(seed);
+            n.0[0] |= 1;
+            moduli.push(n);
+        }
+
+        for (k, modulus) in moduli.into_iter().enumerate() {
+            let ctx = ConstMontgomery::new(modulus);
+            let n = BigUInt::from(modulus);
+            let reduce =
+                |value: ConstUInt<4>| ConstUInt::try_from(BigUInt::from(value) % &n).unwrap();
+
+            for seed in 1..5 {
+                let seed = seed * 31 + k as u64;
+                let (a, b) = (reduce(limbs(seed)), reduce(limbs(seed + 7)));
+                let (big_a, big_b) = (BigUInt::from(a), BigUInt::from(b));
+
+                assert_eq!(BigUInt::from(ctx.modmul(&a, &b)), &big_a * &big_b % &n);
+                assert_eq!(BigUInt::from(ctx.add(&a, &b)), (&big_a + &big_b) % &n);
+                assert_eq!(BigUInt::from(ctx.sub(&a, &b)), (&big_a + &n - &big_b) % &n);
+                assert_eq!(ctx.from_montgomery(&ctx.to_montgomery(&a)), a);
+
+                let exp = limbs::<2>(seed + 13);
+                assert_eq!(
+                    BigUInt::from(ctx.modpow(&a, &exp)),
+                    big_a.modpow(&BigUInt::from(exp), &n)
+                );
+            }
+
+            // unreduced inputs to `to_montgomery` and the edge exponents
+            let big = limbs::<4>(99);
+            let reduced = ctx.from_montgomery(&ctx.to_montgomery(&big));
+            assert_eq!(BigUInt::from(reduced), BigUInt::from(big) % &n);
+            let zero = ConstUInt::<1>::ZERO;
+            assert_eq!(ctx.modpow(&reduce(big), &zero), reduce(ConstUInt::ONE));
+            assert_eq!(ctx.modpow(&reduce(big), &ConstUInt::<1>::ONE), reduce(big));
+        }
+    }
+}
@@ -0,0 +1,771 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, LowerExp, UpperExp};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::rational::{parse_decimal, round_div, BigRational, ParseRationalError, RoundingMode};
+use crate::ArithmeticError;
+
+use super::{BigInt, BigUInt, Pow, Sign};
+
+// mantissa * 10^(-scale); trailing zeros are significant, so 1.50 has mantissa 150 and scale 2.
+// Comparison is by value, 1.50 == 1.5
+#[derive(Clone, Debug)]
+pub struct BigDecimal {
+    mantissa: BigInt,
+    scale: i64,
+}
+
+// number of significant digits and the rounding of inexact results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Context {
+    pub precision: u64,
+    pub rounding: RoundingMode,
+}
+
+impl Context {
+    // rounds half to even like IEEE 754 arithmetic
+    #[must_use]
+    pub fn new(precision: u64) -> Self {
+        assert!(precision > 0, "precision must be positive");
+        Self {
+            precision,
+            rounding: RoundingMode::HalfEven,
+        }
+    }
+
+    #[must_use]
+    pub fn with_rounding(self, rounding: RoundingMode) -> Self {
+        Self { rounding, ..self }
+    }
+}
+
+fn pow10(exp: u64) -> BigUInt {
+    BigUInt::from(10u64).pow(exp)
+}
+
+// value * 10^exp
+fn shift(value: &BigInt, exp: u64) -> BigInt {
+    BigInt::from((&value.inner * &pow10(exp), value.sign)).fix_zero()
+}
+
+// number of decimal digits, 0 for zero
+fn digits(value: &BigUInt) -> u64 {
+    if value.is_zero() {
+        return 0;
+    }
+
+    // value >= 2^(bits - 1) >= 10^count, one is subtracted for floating point errors
+    let estimate = (value.bit_length() - 1) as f64 * std::f64::consts::LOG10_2;
+    let mut count = (estimate as u64).saturating_sub(1);
+    let mut power = pow10(count + 1);
+    while *value >= power {
+        count += 1;
+        power *= 10;
+    }
+    count + 1
+}
+
+// numer / denom rounded to `precision` significant digits as (m, k) with the value m * 10^(-k)
+fn round_quotient(numer: &BigInt, denom: &BigUInt, context: Context) -> (BigInt, i64) {
+    if numer.inner.is_zero() {
+        return (BigInt::from(0), 0);
+    }
+
+    // numer / denom lies in (10^(e - 1), 10^(e + 1)), so with k = precision - e
+    // the integer part of numer * 10^k / denom has `precision` or `precision + 1` digits
+    let e = digits(&numer.inner) as i64 - digits(denom) as i64;
+    let scaled = |k: i64| {
+        if k >= 0 {
+            (shift(numer, k as u64), denom.clone())
+        } else {
+            (numer.clone(), denom * &pow10(k.unsigned_abs()))
+        }
+    };
+    let mut k = context.precision as i64 - e;
+    let (mut n, mut d) = scaled(k);
+    let limit = pow10(context.precision);
+    if n.inner >= &d * &limit {
+        k -= 1;
+        (n, d) = scaled(k);
+    }
+
+    let mut mantissa = round_div(&n, &d, context.rounding);
+    // 99...9 may be rounded up to 10^precision
+    if mantissa.inner == limit {
+        mantissa /= 10;
+        k -= 1;
+    }
+    (mantissa, k)
+}
+
+impl BigDecimal {
+    pub const ZERO: Self = Self {
+        mantissa: BigInt::ZERO,
+        scale: 0,
+    };
+    pub const ONE: Self = Self {
+        mantissa: BigInt::ONE,
+        scale: 0,
+    };
+
+    #[must_use]
+    pub fn new(mantissa: BigInt, scale: i64) -> Self {
+        Self { mantissa, scale }
+    }
+
+    #[must_use]
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+
+    // number of digits after the decimal point, negative for trailing zeros of integers
+    #[must_use]
+    pub fn scale(&self) -> i64 {
+        self.scale
+    }
+
+    // number of significant digits of the mantissa
+    #[must_use]
+    pub fn precision(&self) -> u64 {
+        digits(&self.mantissa.inner)
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.inner.is_zero()
+    }
+
+    // the same value without trailing zeros in the mantissa
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        if self.is_zero() {
+            return Self::from(BigInt::from(0));
+        }
+
+        let mut value = self.clone();
+        loop {
+            let (div, rem) = value.mantissa.inner.clone().div_rem_u64(10);
+            if rem != 0 {
+                return value;
+            }
+            value.mantissa.inner = div;
+            value.scale -= 1;
+        }
+    }
+
+    // the value with `scale` digits after the decimal point, rounded if needed
+    #[must_use]
+    pub fn with_scale(&self, scale: i64, mode: RoundingMode) -> Self {
+        let mantissa = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.mantissa.clone(),
+            Ordering::Greater => shift(&self.mantissa, (scale - self.scale) as u64),
+            Ordering::Less => {
+                let power = pow10((self.scale - scale) as u64);
+                round_div(&self.mantissa, &power, mode)
+            }
+        };
+        Self { mantissa, scale }
+    }
+
+    // the value rounded to at most `context.precision` significant digits
+    #[must_use]
+    pub fn round(&self, context: Context) -> Self {
+        if self.precision() <= context.precision {
+            return self.clone();
+        }
+        let (mantissa, k) = round_quotient(&self.mantissa, &BigUInt::from(1u64), context);
+        Self {
+            mantissa,
+            scale: k + self.scale,
+        }
+    }
+
+    #[must_use]
+    pub fn from_rational(value: &BigRational, context: Context) -> Self {
+        let (mantissa, scale) = round_quotient(value.numer(), value.denom(), context);
+        Self { mantissa, scale }
+    }
+
+    // exact value as a reduced fraction
+    #[must_use]
+    pub fn to_rational(&self) -> BigRational {
+        let power = BigInt::from(pow10(self.scale.unsigned_abs()));
+        if self.scale >= 0 {
+            BigRational::new(self.mantissa.clone(), power)
+        } else {
+            BigRational::from(&self.mantissa * &power)
+        }
+    }
+
+    // self / rhs rounded to `context`, panics if `rhs` is zero
+    #[must_use]
+    pub fn divide(&self, rhs: &Self, context: Context) -> Self {
+        self.checked_divide(rhs, context)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn checked_divide(&self, rhs: &Self, context: Context) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+
+        // (m1 / m2) * 10^(s2 - s1)
+        let sign = if self.mantissa.sign == rhs.mantissa.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        let numer = BigInt::from((self.mantissa.inner.clone(), sign)).fix_zero();
+        let (mantissa, k) = round_quotient(&numer, &rhs.mantissa.inner, context);
+        let quotient = Self {
+            mantissa,
+            scale: k + self.scale - rhs.scale,
+        };
+
+        // an exact quotient loses trailing zeros down to the scale s1 - s2, so 1 / 8 is 0.125
+        if &quotient * rhs != *self {
+            return Ok(quotient);
+        }
+        let ideal = self.scale - rhs.scale;
+        let normalized = quotient.normalized();
+        Ok(if normalized.scale >= ideal {
+            normalized
+        } else {
+            quotient.with_scale(ideal.min(quotient.scale), RoundingMode::Down)
+        })
+    }
+
+    // square root rounded to `context`, panics for negative numbers
+    #[must_use]
+    pub fn sqrt(&self, context: Context) -> Self {
+        self.checked_sqrt(context)
+            .expect("square root of a negative number")
+    }
+
+    // `NegativeOperand` for negative numbers
+    pub fn checked_sqrt(&self, context: Context) -> Result<Self, ArithmeticError> {
+        if self.mantissa.sign == Sign::Minus {
+            return Err(ArithmeticError::NegativeOperand);
+        }
+        if self.is_zero() {
+            return Ok(Self::from(BigInt::from(0)));
+        }
+
+        // sqrt(m * 10^(-s)) = sqrt(m * 10^t) * 10^(-(t + s) / 2) with an even t + s,
+        // m * 10^t has at least 2 * precision + 2 digits, so the root has a guard digit
+        let precision = digits(&self.mantissa.inner);
+        let mut t = (2 * context.precision + 2).saturating_sub(precision) as i64;
+        if (t + self.scale).rem_euclid(2) == 1 {
+            t += 1;
+        }
+        let (root, rem) = (&self.mantissa.inner * &pow10(t as u64)).sqrt_rem();
+
+        // the exact root is root + f with 0 <= f < 1. If f > 0 it can be replaced by
+        // root + 1/2: no rounding boundary lies between them because of the guard digit
+        let doubled = BigInt::from((root << 1) + u64::from(!rem.is_zero()));
+        let (mantissa, k) = round_quotient(&doubled, &BigUInt::from(2u64), context);
+        Ok(Self {
+            mantissa,
+            scale: k + (t + self.scale) / 2,
+        })
+    }
+
+    // sum / (B * Q) of a series from `split`
+    fn series(terms: u64, term: impl Fn(u64) -> [BigInt; 4]) -> (BigInt, BigUInt) {
+        let [_, q, b, t] = split(0, terms, &term);
+        (t, (&b * &q).inner)
+    }
+
+    // pi by the Chudnovsky series, 14 digits per term
+    #[must_use]
+    pub fn pi(context: Context) -> Self {
+        let work = Context::new(context.precision + GUARD_DIGITS);
+        let (t, bq) = Self::series(work.precision / 14 + 2, |k| {
+            if k == 0 {
+                return [1, 1, 1, 13_591_409].map(BigInt::from);
+            }
+            let p = BigInt::from(6 * k - 5) * BigInt::from(2 * k - 1) * BigInt::from(6 * k - 1);
+            let q = BigInt::from(k).pow(3) * BigInt::from(10_939_058_860_032_000u64);
+            let a = BigInt::from(13_591_409 + 545_140_134 * k);
+            [-p, q, BigInt::from(1), a]
+        });
+
+        // pi = 426880 * sqrt(10005) / sum
+        let root = Self::from(BigInt::from(10_005)).sqrt(work);
+        let numer = &root * &Self::from(BigInt::from(426_880) * BigInt::from(bq));
+        numer.divide(&Self::from(t), context)
+    }
+
+    // e = sum 1 / n!
+    #[must_use]
+    pub fn e(context: Context) -> Self {
+        // n! > 10^precision
+        let digits = (context.precision + GUARD_DIGITS) as f64;
+        let mut terms = 1;
+        let mut log = 0.0;
+        while log < digits {
+            terms += 1;
+            log += (terms as f64).log10();
+        }
+
+        let (t, bq) = Self::series(terms + 1, |n| {
+            let q = BigInt::from(n.max(1));
+            [BigInt::from(1), q, BigInt::from(1), BigInt::from(1)]
+        });
+        let (mantissa, scale) = round_quotient(&t, &bq, context);
+        Self { mantissa, scale }
+    }
+
+    // ln 2 = 2 * atanh(1/3) = 2/3 * sum 1 / ((2k + 1) * 9^k), almost a digit per term
+    #[must_use]
+    pub fn ln2(context: Context) -> Self {
+        let terms = ((context.precision + GUARD_DIGITS) as f64 / 9f64.log10()) as u64 + 2;
+        let (t, bq) = Self::series(terms, |k| {
+            let q = if k == 0 { 1 } else { 9 };
+            [1, q, 2 * k + 1, 1].map(BigInt::from)
+        });
+        let (mantissa, scale) = round_quotient(&(t * BigInt::from(2)), &(bq * 3), context);
+        Self { mantissa, scale }
+    }
+}
+
+// extra digits for the intermediate results of constants
+const GUARD_DIGITS: u64 = 10;
+
+// Binary splitting of the series sum a(n) / b(n) * p(0) * ... * p(n) / (q(0) * ... * q(n))
+// over lo <= n < hi. `term(n)` gives [p(n), q(n), b(n), a(n)]; the result is [P, Q, B, T]
+// where P and Q are the products of p and q, B is the product of b and the sum is T / (B * Q)
+fn split(lo: u64, hi: u64, term: &impl Fn(u64) -> [BigInt; 4]) -> [BigInt; 4] {
+    if hi - lo == 1 {
+        let [p, q, b, a] = term(lo);
+        let t = &a * &p;
+        return [p, q, b, t];
+    }
+
+    let mid = lo + (hi - lo) / 2;
+    let [p1, q1, b1, t1] = split(lo, mid, term);
+    let [p2, q2, b2, t2] = split(mid, hi, term);
+    let t = &(&b2 * &q2) * &t1 + &(&b1 * &p1) * &t2;
+    [&p1 * &p2, &q1 * &q2, &b1 * &b2, t]
+}
+
+impl From<BigInt> for BigDecimal {
+    fn from(value: BigInt) -> Self {
+        Self::new(value, 0)
+    }
+}
+
+impl From<BigUInt> for BigDecimal {
+    fn from(value: BigUInt) -> Self {
+        Self::new(BigInt::from(value), 0)
+    }
+}
+
+// mantissas of both values at the larger of their scales
+fn aligned(lhs: &BigDecimal, rhs: &BigDecimal) -> (BigInt, BigInt, i64) {
+    match lhs.scale.cmp(&rhs.scale) {
+        Ordering::Equal => (lhs.mantissa.clone(), rhs.mantissa.clone(), lhs.scale),
+        Ordering::Less => {
+            let lhs_mantissa = shift(&lhs.mantissa, (rhs.scale - lhs.scale) as u64);
+            (lhs_mantissa, rhs.mantissa.clone(), rhs.scale)
+        }
+        Ordering::Greater => {
+            let rhs_mantissa = shift(&rhs.mantissa, (lhs.scale - rhs.scale) as u64);
+            (lhs.mantissa.clone(), rhs_mantissa, lhs.scale)
+        }
+    }
+}
+
+// exact, the scale of the result is the larger of the scales
+impl Add<&BigDecimal> for &BigDecimal {
+    type Output = BigDecimal;
+
+    fn add(self, rhs: &BigDecimal) -> BigDecimal {
+        let (lhs, rhs, scale) = aligned(self, rhs);
+        BigDecimal::new(lhs + rhs, scale)
+    }
+}
+
+impl Sub<&BigDecimal> for &BigDecimal {
+    type Output = BigDecimal;
+
+    fn sub(self, rhs: &BigDecimal) -> BigDecimal {
+        let (lhs, rhs, scale) = aligned(self, rhs);
+        BigDecimal::new(lhs - rhs, scale)
+    }
+}
+
+// exact, scales are added
+impl Mul<&BigDecimal> for &BigDecimal {
+    type Output = BigDecimal;
+
+    fn mul(self, rhs: &BigDecimal) -> BigDecimal {
+        BigDecimal::new(&self.mantissa * &rhs.mantissa, self.scale + rhs.scale)
+    }
+}
+
+impl Neg for BigDecimal {
+    type Output = BigDecimal;
+
+    fn neg(self) -> BigDecimal {
+        Self::new(-self.mantissa, self.scale)
+    }
+}
+
+impl Neg for &BigDecimal {
+    type Output = BigDecimal;
+
+    fn neg(self) -> BigDecimal {
+        -self.clone()
+    }
+}
+
+impl Ord for BigDecimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (lhs, rhs, _) = aligned(self, other);
+        lhs.cmp(&rhs)
+    }
+}
+
+impl PartialOrd for BigDecimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for BigDecimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BigDecimal {}
+
+// equal values have the same normalized form
+impl Hash for BigDecimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl Default for BigDecimal {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+// "[-]digits[.digits][(e|E)[-]digits]", the scale is the number of written fractional digits
+impl FromStr for BigDecimal {
+    type Err = ParseRationalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = s.len() - s.trim_start().len();
+        let (mantissa, exp) = parse_decimal(s.trim(), offset)?;
+        Ok(Self::new(mantissa, -exp))
+    }
+}
+
+impl BigDecimal {
+    // digits of the absolute value without exponent
+    fn plain_digits(&self) -> String {
+        let digits = self.mantissa.inner.to_string();
+        if self.scale <= 0 {
+            return digits + &"0".repeat(self.scale.unsigned_abs() as usize);
+        }
+
+        let scale = self.scale as usize;
+        if scale >= digits.len() {
+            format!("0.{}{digits}", "0".repeat(scale - digits.len()))
+        } else {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            format!("{int}.{frac}")
+        }
+    }
+
+    // d.ddd followed by the exponent, `precision` is the number of fractional digits
+    fn exp_digits(&self, precision: Option<usize>, marker: char) -> String {
+        let value = match precision {
+            // the digits of a zero mantissa are always "0", so padding would only lower
+            // the exponent
+            Some(0) if self.is_zero() => return format!("0{marker}0"),
+            Some(precision) if self.is_zero() => {
+                return format!("0.{}{marker}0", "0".repeat(precision));
+            }
+            Some(precision) => {
+                let context = Context::new(precision as u64 + 1);
+                let rounded = self.round(context);
+                // pad with zeros up to the requested number of digits
+                let missing = (precision as u64 + 1).saturating_sub(rounded.precision().max(1));
+                rounded.with_scale(rounded.scale + missing as i64, RoundingMode::HalfEven)
+            }
+            None => self.clone(),
+        };
+
+        let digits = value.mantissa.inner.to_string();
+        let exp = digits.len() as i64 - 1 - value.scale;
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{first}{marker}{exp}")
+        } else {
+            format!("{first}.{rest}{marker}{exp}")
+        }
+    }
+}
+
+// plain notation, the precision flag sets the number of fractional digits (rounding half to even)
+impl Display for BigDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = match f.precision() {
+            Some(precision) => self
+                .with_scale(precision as i64, RoundingMode::HalfEven)
+                .plain_digits(),
+            None => self.plain_digits(),
+        };
+        f.pad_integral(self.mantissa.sign == Sign::Plus, "", &digits)
+    }
+}
+
+// scientific notation like `1.25e-3`
+impl LowerExp for BigDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.exp_digits(f.precision(), 'e');
+        f.pad_integral(self.mantissa.sign == Sign::Plus, "", &digits)
+    }
+}
+
+impl UpperExp for BigDecimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = self.exp_digits(f.precision(), 'E');
+        f.pad_integral(self.mantissa.sign == Sign::Plus, "", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> BigDecimal {
+        s.parse().unwrap()
+    }
+
+    // rounded to 100 fractional digits
+    const PI_100: &str = "3.1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170680";
+    const E_100: &str = "2.7182818284590452353602874713526624977572470936999595749669676277240766303535475945713821785251664274";
+    const LN2_100: &str = "0.6931471805599453094172321214581765680755001343602552541206800094933936219696947156058633269964186875";
+
+    #[test]
+    fn parse_display_test() {
+        for s in ["123.4500", "-0.00123", "0", "42", "-7.0", "0.5"] {
+            assert_eq!(dec(s).to_string(), s);
+        }
+        assert_eq!(dec("123.4500").scale(), 4);
+        assert_eq!(dec("1.5e3").to_string(), "1500");
+        assert_eq!(dec("1.5e3").scale(), -2);
+        assert_eq!(dec("-25E-4").to_string(), "-0.0025");
+        assert_eq!(format!("{:.2}", dec("2.345")), "2.34");
+        assert_eq!(format!("{:.3}", dec("2.5")), "2.500");
+        assert_eq!(format!("{:>8}", dec("-1.25")), "   -1.25");
+        assert_eq!(
+            "1.2.3".parse::<BigDecimal>(),
+            Err(ParseRationalError::InvalidDigit { position: 3 })
+        );
+        assert_eq!("".parse::<BigDecimal>(), Err(ParseRationalError::Empty));
+    }
+
+    #[test]
+    fn scientific_test() {
+        assert_eq!(format!("{:e}", dec("1500")), "1.500e3");
+        assert_eq!(format!("{:e}", dec("1.5e3")), "1.5e3");
+        assert_eq!(format!("{:e}", dec("-0.00123")), "-1.23e-3");
+        assert_eq!(format!("{:E}", dec("7")), "7E0");
+        assert_eq!(format!("{:e}", dec("0")), "0e0");
+        assert_eq!(format!("{:.1e}", dec("12345")), "1.2e4");
+        assert_eq!(format!("{:.3e}", dec("1.5")), "1.500e0");
+        assert_eq!(format!("{:.2e}", dec("9.999")), "1.00e1");
+        assert_eq!(format!("{:.2e}", BigDecimal::ZERO), "0.00e0");
+        assert_eq!(format!("{:.1e}", dec("0.00")), "0.0e0");
+        assert_eq!(format!("{:.0E}", dec("0e5")), "0E0");
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        assert_eq!(dec("0.1") + dec("0.2"), dec("0.3"));
+        assert_eq!((dec("0.1") + dec("0.20")).to_string(), "0.30");
+        assert_eq!((dec("1.5") - dec("2.25")).to_string(), "-0.75");
+        assert_eq!((dec("1.5") * dec("-0.25")).to_string(), "-0.375");
+        assert_eq!((dec("1.5e3") * dec("2")).to_string(), "3000");
+        assert_eq!(-dec("1.5"), dec("-1.50"));
+
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert!(dec("1.5") < dec("1.51"));
+        assert!(dec("-2") < dec("-1.99"));
+        assert!(dec("1e3") > dec("999.999"));
+        assert_eq!(dec("1.500").normalized().to_string(), "1.5");
+        assert_eq!(dec("1.5e3").normalized().scale(), -2);
+        assert_eq!(dec("0.000").normalized().scale(), 0);
+
+        assert_eq!(
+            dec("-1.25").to_rational(),
+            BigRational::new(BigInt::from(-5), BigInt::from(4))
+        );
+        assert_eq!(
+            dec("1.2e3").to_rational(),
+            BigRational::from(BigInt::from(1200))
+        );
+    }
+
+    #[test]
+    fn rounding_test() {
+        use RoundingMode::*;
+        for (value, mode, expected) in [
+            ("2.5", HalfEven, "2"),
+            ("3.5", HalfEven, "4"),
+            ("2.5", HalfUp, "3"),
+            ("2.5", HalfDown, "2"),
+            ("-2.5", HalfUp, "-3"),
+            ("-2.5", Floor, "-3"),
+            ("-2.5", Ceiling, "-2"),
+            ("-2.1", Up, "-3"),
+            ("2.9", Down, "2"),
+        ] {
+            assert_eq!(
+                dec(value).with_scale(0, mode).to_string(),
+                expected,
+                "{value} {mode:?}"
+            );
+        }
+        assert_eq!(dec("1.005").with_scale(2, HalfUp).to_string(), "1.01");
+        assert_eq!(dec("1.5").with_scale(3, HalfUp).to_string(), "1.500");
+
+        let context = Context::new(3);
+        assert_eq!(dec("123456").round(context), dec("1.23e5"));
+        assert_eq!(dec("123456").round(context).scale(), -3);
+        assert_eq!(dec("99999").round(context).to_string(), "100000");
+        assert_eq!(dec("99999").round(context).precision(), 3);
+        assert_eq!(dec("0.0012345").round(context).to_string(), "0.00123");
+        assert_eq!(dec("1.5").round(context).to_string(), "1.5");
+    }
+
+    #[test]
+    fn divide_test() {
+        let context = Context::new(10);
+        assert_eq!(
+            dec("1").divide(&dec("3"), context).to_string(),
+            "0.3333333333"
+        );
+        assert_eq!(
+            dec("2").divide(&dec("3"), context).to_string(),
+            "0.6666666667"
+        );
+        assert_eq!(
+            dec("2")
+                .divide(&dec("3"), context.with_rounding(RoundingMode::Down))
+                .to_string(),
+            "0.6666666666"
+        );
+        assert_eq!(dec("-1").divide(&dec("8"), context).to_string(), "-0.125");
+        assert_eq!(dec("6.00").divide(&dec("2"), context).to_string(), "3.00");
+        assert_eq!(
+            dec("1e10").divide(&dec("0.5"), context).to_string(),
+            "20000000000"
+        );
+        assert_eq!(
+            dec("1").divide(&dec("7"), Context::new(20)).to_string(),
+            "0.14285714285714285714"
+        );
+        assert_eq!(
+            dec("1").checked_divide(&dec("0.00"), context),
+            Err(ArithmeticError::DivisionByZero)
+        );
+
+        let third = BigRational::new(BigInt::from(-1), BigInt::from(3));
+        assert_eq!(
+            BigDecimal::from_rational(&third, Context::new(5)).to_string(),
+            "-0.33333"
+        );
+    }
+
+    #[test]
+    fn sqrt_test() {
+        let two = dec("2").sqrt(Context::new(100));
+        // from Python's `decimal` module
+        assert_eq!(
+            two.to_string(),
+            "1.414213562373095048801688724209698078569671875376948073176679737990732478462107038850387534327641573"
+        );
+        assert_eq!(dec("0.0004").sqrt(Context::new(5)), dec("0.02"));
+        assert_eq!(dec("1e-7").sqrt(Context::new(5)).to_string(), "0.00031623");
+        assert_eq!(dec("15.9999").sqrt(Context::new(2)).to_string(), "4.0");
+        assert_eq!(
+            dec("2")
+                .sqrt(Context::new(3).with_rounding(RoundingMode::Up))
+                .to_string(),
+            "1.42"
+        );
+        assert_eq!(dec("0").sqrt(Context::new(3)), dec("0"));
+        assert_eq!(
+            dec("-4").checked_sqrt(Context::new(3)),
+            Err(ArithmeticError::NegativeOperand)
+        );
+    }
+
+    // pi / 4 = 4 * atan(1/5) - atan(1/239) in fixed point with `digits` digits
+    fn machin_pi(digits: u64) -> BigInt {
+        let one = BigInt::from(pow10(digits));
+        let atan = |x: u64| {
+            let mut sum = BigInt::from(0);
+            let mut power = &one / &BigInt::from(x);
+            let mut k = 0u64;
+            while !power.inner.is_zero() {
+                let term = &power / &BigInt::from(2 * k + 1);
+                sum = if k.is_multiple_of(2) {
+                    sum + term
+                } else {
+                    sum - term
+                };
+                power /= BigInt::from(x * x);
+                k += 1;
+            }
+            sum
+        };
+        (atan(5) * BigInt::from(16)) - atan(239) * BigInt::from(4)
+    }
+
+    #[test]
+    fn constants_test() {
+        let context = Context::new(101);
+        assert_eq!(BigDecimal::pi(context).to_string(), PI_100);
+        assert_eq!(BigDecimal::e(context).to_string(), E_100);
+        assert_eq!(BigDecimal::ln2(Context::new(100)).to_string(), LN2_100);
+
+        // independent slow series agree on the first 1000 digits
+        let context = Context::new(1000).with_rounding(RoundingMode::Down);
+        let pi = BigDecimal::pi(context);
+        let machin = BigDecimal::new(machin_pi(1010), 1010);
+        assert_eq!(pi, machin.round(context));
+
+        let mut e = BigInt::from(0);
+        let mut term = BigInt::from(pow10(1010));
+        for n in 1..500 {
+            e += &term;
+            term /= BigInt::from(n);
+        }
+        assert_eq!(
+            BigDecimal::e(context),
+            BigDecimal::new(e, 1010).round(context)
+        );
+
+        // ln 2 = sum 1 / (k * 2^k)
+        let mut ln2 = BigInt::from(0);
+        let one = BigInt::from(pow10(1010));
+        for k in 1..3500u64 {
+            ln2 += &(&one / &(BigInt::from(k) << k as u32));
+        }
+        let ln2 = BigDecimal::new(ln2, 1010).round(context);
+        assert_eq!(BigDecimal::ln2(context), ln2);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::helpers::shl_n64;
+use crate::mul::mul;
+
+use super::limbs::Limbs;
+use super::BigUInt;
+
+// Division algorithms used by `BigUInt::div_rem`.
+// Single-limb divisors use 2-by-1 division with a precomputed reciprocal,
+// multi-limb divisors use Knuth's Algorithm D and huge ones
+// Burnikel-Ziegler recursive division on top of it.
+
+// divisors (and quotients) shorter than this are handled by Algorithm D
+const BURNIKEL_ZIEGLER_THRESHOLD: usize = 64;
+
+fn trim(mut limbs: Vec<u64>) -> Vec<u64> {
+    while limbs.last() == Some(&0) {
+        let _ = limbs.pop();
+    }
+    limbs
+}
+
+// floor((2^128 - 1) / d) - 2^64 for normalized `d` (highest bit set)
+fn reciprocal(d: u64) -> u64 {
+    debug_assert!(d >> 63 == 1);
+    (u128::MAX / d as u128) as u64
+}
+
+// divides (hi, lo) by normalized `d` with reciprocal `v`, requires hi < d
+// Möller & Granlund, "Improved division by invariant integers"
+fn div_2by1(hi: u64, lo: u64, d: u64, v: u64) -> (u64, u64) {
+    let q = (v as u128 * hi as u128).wrapping_add(((hi as u128) << 64) | lo as u128);
+    let (mut q1, q0) = (((q >> 64) as u64).wrapping_add(1), q as u64);
+    let mut r = lo.wrapping_sub(q1.wrapping_mul(d));
+
+    if r > q0 {
+        q1 = q1.wrapping_sub(1);
+        r = r.wrapping_add(d);
+    }
+    if r >= d {
+        q1 += 1;
+        r -= d;
+    }
+
+    (q1, r)
+}
+
+// divides limbs by a single non-zero limb
+pub(crate) fn div_rem_limb(limbs: &[u64], d: u64) -> (Limbs, u64) {
+    let mut quotient = Limbs::from(limbs);
+    let rem = div_rem_limb_in_place(&mut quotient, d);
+    (quotient, rem)
+}
+
+// remainder only, limbs are left untouched
+pub(crate) fn rem_limb(limbs: &[u64], d: u64) -> u64 {
+    assert!(d != 0, "Division by zero");
+
+    limbs.iter().rev().fold(0, |rem, &limb| {
+        (((rem as u128) << 64 | limb as u128) % d as u128) as u64
+    })
+}
+
+// replaces limbs with the quotient and returns the remainder
+pub(crate) fn div_rem_limb_in_place(limbs: &mut Limbs, d: u64) -> u64 {
+    assert!(d != 0, "Division by zero");
+
+    let shift = d.leading_zeros();
+    let d = d << shift;
+    let v = reciprocal(d);
+
+    let mut rem = 0;
+
+    for limb in limbs.iter_mut().rev() {
+        let (hi, lo) = if shift == 0 {
+            (rem, *limb)
+        } else {
+            // `rem` is already shifted, only bits of `limb` have to be brought in
+            (rem | (*limb >> (64 - shift)), *limb << shift)
+        };
+        (*limb, rem) = div_2by1(hi, lo, d, v);
+    }
+
+    while limbs.last() == Some(&0) {
+        let _ = limbs.pop();
+    }
+    rem >> shift
+}
+
+// shifts limbs left by `shift` < 64 bits, the result has one more limb
+fn shl_bits(limbs: &[u64], shift: u32) -> Vec<u64> {
+    let mut res = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        if shift == 0 {
+            res.push(limb);
+        } else {
+            res.push((limb << shift) | carry);
+            carry = limb >> (64 - shift);
+        }
+    }
+    res.push(carry);
+    res
+}
+
+fn shr_bits(limbs: &mut [u64], shift: u32) {
+    if shift == 0 {
+        return;
+    }
+    for i in 0..limbs.len() {
+        let next = limbs.get(i + 1).copied().unwrap_or(0);
+        limbs[i] = (limbs[i] >> shift) | (next << (64 - shift));
+    }
+}
+
+// Knuth's Algorithm D (TAOCP vol. 2, 4.3.1), requires divisor to have at least 2 limbs
+pub(crate) fn div_rem_knuth(u: &[u64], v: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let mut quotient = Limbs::from(u);
+    let rem = div_rem_knuth_in_place(&mut quotient, v);
+    (quotient.into_vec(), rem)
+}
+
+// Algorithm D that replaces `u` with the quotient and returns the remainder
+pub(crate) fn div_rem_knuth_in_place(u: &mut Limbs, v: &[u64]) -> Vec<u64> {
+    let n = v.len();
+    debug_assert!(n >= 2 && v[n - 1] != 0);
+
+    if u.len() < n {
+        let rem = u.to_vec();
+        u.clear();
+        return rem;
+    }
+
+    // D1: normalize so the highest bit of the divisor is set
+    let shift = v[n - 1].leading_zeros();
+    let mut vn = shl_bits(v, shift);
+    let _ = vn.pop();
+    let mut un = shl_bits(u, shift);
+
+    let (d1, d0) = (vn[n - 1], vn[n - 2]);
+    let recip = reciprocal(d1);
+    let m = un.len() - n - 1;
+    // `un` holds a copy of the dividend, so its limbs can take the quotient
+    let quotient = u;
+    quotient.clear();
+    quotient.resize(m + 1, 0);
+
+    for j in (0..=m).rev() {
+        // D3: estimate quotient digit from the top two limbs
+        let (u2, u1, u0) = (un[j + n], un[j + n - 1], un[j + n - 2]);
+        let (mut qhat, mut rhat) = if u2 >= d1 {
+            (u64::MAX, u1.checked_add(d1))
+        } else {
+            let (qhat, rhat) = div_2by1(u2, u1, d1, recip);
+            (qhat, Some(rhat))
+        };
+        while let Some(r) = rhat {
+            if (qhat as u128 * d0 as u128) <= (((r as u128) << 64) | u0 as u128) {
+                break;
+            }
+            qhat -= 1;
+            rhat = r.checked_add(d1);
+        }
+
+        // D4: multiply and subtract
+        let mut borrow = false;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let prod = qhat as u128 * vn[i] as u128 + carry as u128;
+            carry = (prod >> 64) as u64;
+            (un[j + i], borrow) = un[j + i].borrowing_sub(prod as u64, borrow);
+        }
+        let (top, b1) = un[j + n].overflowing_sub(carry);
+        let (top, b2) = top.overflowing_sub(borrow as u64);
+        un[j + n] = top;
+
+        // D6: add back, happens with probability ~2/2^64
+        if b1 || b2 {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                (un[j + i], carry) = un[j + i].carrying_add(vn[i], carry);
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+
+        quotient[j] = qhat;
+    }
+
+    while quotient.last() == Some(&0) {
+        let _ = quotient.pop();
+    }
+
+    // D8: unnormalize remainder
+    un.truncate(n);
+    shr_bits(&mut un, shift);
+    trim(un)
+}
+
+// (low `n` limbs, the rest)
+fn split(value: &BigUInt, n: usize) -> (BigUInt, BigUInt) {
+    let n = n.min(value.0.len());
+    (
+        BigUInt::from(trim(value.0[..n].to_vec())),
+        BigUInt::from(value.0[n..].to_vec()),
+    )
+}
+
+// value * 2^(64 * n) + low
+fn join(value: BigUInt, n: usize, low: BigUInt) -> BigUInt {
+    if value.is_zero() {
+        low
+    } else {
+        shl_n64(value, n) + low
+    }
+}
+
+// divides `a` < b * 2^(64 * n) by normalized `b` of `n` limbs
+fn div_2n_1n(a: BigUInt, b: &BigUInt, n: usize) -> (BigUInt, BigUInt) {
+    if n % 2 == 1 || n < BURNIKEL_ZIEGLER_THRESHOLD {
+        let mut q = a;
+        let r = div_rem_knuth_in_place(&mut q.0, &b.0);
+        return (q, BigUInt::from(r));
+    }
+
+    let half = n / 2;
+    let (a4, a123) = split(&a, half);
+    let (q1, r) = div_3n_2n(a123, b, half);
+    let (q2, s) = div_3n_2n(join(r, half, a4), b, half);
+
+    (join(q1, half, q2), s)
+}
+
+// divides `a` < b * 2^(64 * half) by normalized `b` of `2 * half` limbs
+fn div_3n_2n(a: BigUInt, b: &BigUInt, half: usize) -> (BigUInt, BigUInt) {
+    let (b2, b1) = split(b, half);
+    let (a3, a12) = split(&a, half);
+    let (_, a1) = split(&a12, half);
+
+    let (mut q, r1) = if a1 < b1 {
+        div_2n_1n(a12, &b1, half)
+    } else {
+        // quotient digit is 2^(64 * half) - 1
+        let q = BigUInt::from(vec![u64::MAX; half]);
+        let r1 = a12 + &b1 - shl_n64(b1, half);
+        (q, r1)
+    };
+
+    let d = mul(&q.0, &b2.0);
+    let mut r = join(r1, half, a3);
+    while r < d {
+        r += b;
+        q -= 1;
+    }
+
+    (q, r - d)
+}
+
+// Burnikel & Ziegler, "Fast Recursive Division"
+pub(crate) fn div_rem_recursive(u: &BigUInt, v: &BigUInt) -> (BigUInt, BigUInt) {
+    // pad divisor to `block << levels` limbs so every level splits evenly
+    let len = v.0.len();
+    let mut levels = 0;
+    while (len >> levels) > BURNIKEL_ZIEGLER_THRESHOLD {
+        levels += 1;
+    }
+    let n = len.div_ceil(1 << levels) << levels;
+    let shift = ((n - len) * 64) as u32 + v.0[len - 1].leading_zeros();
+
+    let b = v << shift;
+    let a = u << shift;
+    debug_assert_eq!(b.0.len(), n);
+
+    // the highest block has less than `n` limbs, so it's less than `b`
+    let blocks = (a.0.len() / n + 1).max(2);
+    let block = |i: usize| split(&split(&a, (i + 1) * n).0, i * n).1;
+
+    let mut quotient = vec![0; blocks * n];
+    let mut z = join(block(blocks - 1), n, block(blocks - 2));
+    for i in (0..blocks - 1).rev() {
+        let (q, r) = div_2n_1n(z, &b, n);
+        quotient[i * n..i * n + q.0.len()].copy_from_slice(&q.0);
+        z = if i > 0 { join(r, n, block(i - 1)) } else { r };
+    }
+
+    (BigUInt::from(trim(quotient)), z >> shift)
+}
+
+pub(crate) fn div_rem(u: &BigUInt, v: &BigUInt) -> (BigUInt, BigUInt) {
+    match v.0.len() {
+        0 => panic!("Division by zero"),
+        _ if u < v => (BigUInt::from(0u64), u.clone()),
+        1 => {
+            let (q, r) = div_rem_limb(&u.0, v.0[0]);
+            (BigUInt(q), BigUInt::from(r))
+        }
+        len if len >= BURNIKEL_ZIEGLER_THRESHOLD
+            && u.0.len() - len >= BURNIKEL_ZIEGLER_THRESHOLD =>
+        {
+            div_rem_recursive(u, v)
+        }
+        _ => {
+            let (q, r) = div_rem_knuth(&u.0, &v.0);
+            (BigUInt::from(q), BigUInt::from(r))
+        }
+    }
+}
+
+// `div_rem` that replaces `u` with the quotient and returns the remainder, for `/=`.
+// Only Burnikel-Ziegler division needs a separate buffer for the quotient
+pub(crate) fn div_rem_in_place(u: &mut BigUInt, v: &BigUInt) -> BigUInt {
+    match v.0.len() {
+        0 => panic!("Division by zero"),
+        _ if *u < *v => std::mem::take(u),
+        1 => BigUInt::from(div_rem_limb_in_place(&mut u.0, v.0[0])),
+        len if len >= BURNIKEL_ZIEGLER_THRESHOLD
+            && u.0.len() - len >= BURNIKEL_ZIEGLER_THRESHOLD =>
+        {
+            let (q, r) = div_rem_recursive(u, v);
+            *u = q;
+            r
+        }
+        _ => BigUInt::from(div_rem_knuth_in_place(&mut u.0, &v.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::limbs;
+
+    fn check(u: &BigUInt, v: &BigUInt, (q, r): (BigUInt, BigUInt)) {
+        assert!(&r < v);
+        assert_eq!(q * v.clone() + r, u.clone());
+    }
+
+    #[test]
+    fn div_rem_limb_test() {
+        assert_eq!(div_rem_limb(&[], 7), (vec![].into(), 0));
+        assert_eq!(div_rem_limb(&[123_456], 47), (vec![2626].into(), 34));
+        assert_eq!(div_rem_limb(&[0, 1], u64::MAX), (vec![1].into(), 1));
+        assert_eq!(
+            div_rem_limb(&[u64::MAX, u64::MAX], 1 << 63),
+            (vec![u64::MAX, 1].into(), (1 << 63) - 1)
+        );
+
+        let u = BigUInt::from(limbs(1, 20));
+        for d in [1, 3, 10, 0x1234_5678, u64::MAX] {
+            let (q, r) = div_rem_limb(&u.0, d);
+            check(&u, &BigUInt::from(d), (BigUInt(q), BigUInt::from(r)));
+            assert_eq!(rem_limb(&u.0, d), r);
+        }
+    }
+
+    #[test]
+    fn div_rem_knuth_test() {
+        assert_eq!(div_rem_knuth(&[1, 2], &[3, 4]), (vec![], vec![1, 2]));
+        assert_eq!(div_rem_knuth(&[0, 0, 1], &[0, 1]), (vec![0, 1], vec![]));
+
+        // quotient digit estimation overflows
+        let u = BigUInt::from(vec![0, 0, u64::MAX - 1, u64::MAX]);
+        let v = BigUInt::from(vec![u64::MAX, u64::MAX]);
+        let (q, r) = div_rem_knuth(&u.0, &v.0);
+        check(&u, &v, (BigUInt::from(q), BigUInt::from(r)));
+
+        // add back step
+        let u = BigUInt::from(vec![0, 0, 0x8000_0000_0000_0000, 0x7FFF_FFFF_FFFF_FFFF]);
+        let v = BigUInt::from(vec![1, 0, 0x8000_0000_0000_0000]);
+        let (q, r) = div_rem_knuth(&u.0, &v.0);
+        check(&u, &v, (BigUInt::from(q), BigUInt::from(r)));
+
+        for (ul, vl) in [(2, 2), (5, 3), (40, 17), (100, 99)] {
+            let u = BigUInt::from(limbs(ul as u64, ul));
+            let v = BigUInt::from(limbs(vl as u64 + 100, vl));
+            let (q, r) = div_rem_knuth(&u.0, &v.0);
+            check(&u, &v, (BigUInt::from(q), BigUInt::from(r)));
+        }
+    }
+
+    #[test]
+    fn div_rem_recursive_test() {
+        for (ul, vl) in [(130, 64), (300, 65), (400, 150), (257, 128)] {
+            let u = BigUInt::from(limbs(ul as u64, ul));
+            let v = BigUInt::from(limbs(vl as u64 + 100, vl));
+            let expected = div_rem_knuth(&u.0, &v.0);
+            let (q, r) = div_rem_recursive(&u, &v);
+            assert_eq!((q.0.into_vec(), r.0.into_vec()), expected);
+        }
+
+        let u = BigUInt::from(vec![u64::MAX; 260]);
+        let v = BigUInt::from(vec![u64::MAX; 129]);
+        let expected = div_rem_knuth(&u.0, &v.0);
+        let (q, r) = div_rem_recursive(&u, &v);
+        assert_eq!((q.0.into_vec(), r.0.into_vec()), expected);
+    }
+}
@@ -0,0 +1,245 @@
+use super::{BigInt, BigUInt, Sign};
+
+// leading 63 bits of `value` starting from bit `shift`, so that cofactors fit into i64
+fn top_bits(value: &BigUInt, shift: u32) -> u64 {
+    let (limb, bit) = ((shift / 64) as usize, shift % 64);
+    let low = value.0.get(limb).copied().unwrap_or(0) >> bit;
+    let high = match (bit, value.0.get(limb + 1)) {
+        (0, _) | (_, None) => 0,
+        (_, Some(&next)) => next << (64 - bit),
+    };
+    (low | high) & (u64::MAX >> 1)
+}
+
+fn scale(value: &BigInt, factor: i64) -> BigInt {
+    let scaled = value.clone() * factor.unsigned_abs();
+    if factor < 0 {
+        -scaled
+    } else {
+        scaled
+    }
+}
+
+// (a * x + b * y, c * x + d * y)
+fn combine(x: &BigInt, y: &BigInt, [a, b, c, d]: [i64; 4]) -> (BigInt, BigInt) {
+    (scale(x, a) + scale(y, b), scale(x, c) + scale(y, d))
+}
+
+// single precision part of Lehmer's algorithm (Knuth, Algorithm L)
+// returns cofactors of the steps which are guaranteed to match the multiprecision ones
+fn lehmer_step(mut u: i128, mut v: i128) -> [i64; 4] {
+    let (mut a, mut b, mut c, mut d) = (1i128, 0i128, 0i128, 1i128);
+    while v + c != 0 && v + d != 0 {
+        let q = (u + a) / (v + c);
+        if q != (u + b) / (v + d) {
+            break;
+        }
+        (a, c) = (c, a - q * c);
+        (b, d) = (d, b - q * d);
+        (u, v) = (v, u - q * v);
+    }
+    [a, b, c, d].map(|x| x as i64)
+}
+
+// Lehmer's gcd of u >= v, with the coefficient `s` of `u` in g = s * u + t * v if requested
+fn lehmer(u: &BigUInt, v: &BigUInt, extended: bool) -> (BigUInt, BigInt) {
+    let mut u = BigInt::from(u.clone());
+    let mut v = BigInt::from(v.clone());
+    let mut s_u = BigInt::from(1);
+    let mut s_v = BigInt::from(0);
+
+    while !v.inner.is_zero() {
+        let cofactors = if v.inner.0.len() > 1 {
+            let shift = u.inner.bits_num() - 63;
+            lehmer_step(
+                top_bits(&u.inner, shift).into(),
+                top_bits(&v.inner, shift).into(),
+            )
+        } else {
+            [1, 0, 0, 1]
+        };
+
+        if cofactors[1] == 0 {
+            // no single precision step was possible, do a full division
+            let (q, r) = crate::div::div_rem(&u.inner, &v.inner);
+            u = std::mem::replace(&mut v, BigInt::from(r));
+            if extended {
+                let s = &s_u - &(&BigInt::from(q) * &s_v);
+                s_u = std::mem::replace(&mut s_v, s);
+            }
+        } else {
+            (u, v) = combine(&u, &v, cofactors);
+            if extended {
+                (s_u, s_v) = combine(&s_u, &s_v, cofactors);
+            }
+        }
+    }
+
+    (u.inner, s_u)
+}
+
+impl BigUInt {
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (u, v) = if self >= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        lehmer(u, v, false).0
+    }
+
+    // lcm(0, x) is 0
+    #[must_use]
+    pub fn lcm(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::from(0u64);
+        }
+        &(self / &self.gcd(other)) * other
+    }
+
+    // (g, x, y) such that self * x + other * y = g = gcd(self, other)
+    #[must_use]
+    pub fn extended_gcd(&self, other: &Self) -> (Self, BigInt, BigInt) {
+        let swapped = self < other;
+        let (u, v) = if swapped {
+            (other, self)
+        } else {
+            (self, other)
+        };
+
+        let (g, s) = lehmer(u, v, true);
+        // t = (g - s * u) / v is exact
+        let t = if v.is_zero() {
+            BigInt::from(0)
+        } else {
+            let (t, _) = (BigInt::from(g.clone()) - &s * &BigInt::from(u.clone()))
+                .div_rem_ref(&BigInt::from(v.clone()));
+            t
+        };
+
+        if swapped {
+            (g, t, s)
+        } else {
+            (g, s, t)
+        }
+    }
+}
+
+impl BigInt {
+    // always non-negative
+    #[must_use]
+    pub fn gcd(&self, other: &Self) -> Self {
+        Self::from(self.inner.gcd(&other.inner))
+    }
+
+    // always non-negative
+    #[must_use]
+    pub fn lcm(&self, other: &Self) -> Self {
+        Self::from(self.inner.lcm(&other.inner))
+    }
+
+    // (g, x, y) such that self * x + other * y = g = gcd(self, other), g is non-negative
+    #[must_use]
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (g, x, y) = self.inner.extended_gcd(&other.inner);
+        let x = if self.sign == Sign::Minus { -x } else { x };
+        let y = if other.sign == Sign::Minus { -y } else { y };
+        (Self::from(g), x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::limbs;
+
+    fn big(value: u128) -> BigUInt {
+        (BigUInt::from((value >> 64) as u64) << 64) + value as u64
+    }
+
+    fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    fn check_extended(a: &BigUInt, b: &BigUInt) {
+        let (g, x, y) = a.extended_gcd(b);
+        assert_eq!(
+            BigInt::from(a.clone()) * x + BigInt::from(b.clone()) * y,
+            BigInt::from(g.clone())
+        );
+        if !g.is_zero() {
+            assert_eq!(a % &g, BigUInt::from(0u64));
+            assert_eq!(b % &g, BigUInt::from(0u64));
+        }
+    }
+
+    #[test]
+    fn gcd_test() {
+        for (a, b) in [
+            (0u128, 0u128),
+            (0, 5),
+            (12, 18),
+            (1 << 100, 3 << 70),
+            (u128::MAX, u64::MAX as u128),
+            (
+                0x1234_5678_9ABC_DEF0_1234_5678_9ABC_DEF0,
+                0xFEDC_BA98_7654_3210,
+            ),
+        ] {
+            let expected = big(gcd_u128(a, b));
+            let (a, b) = (big(a), big(b));
+            assert_eq!(a.gcd(&b), expected);
+            assert_eq!(b.gcd(&a), expected);
+            check_extended(&a, &b);
+            check_extended(&b, &a);
+        }
+
+        // common factor of many limbs
+        let common = BigUInt::from(limbs(1, 40));
+        let a = &common * &BigUInt::from(limbs(2, 300));
+        let b = &common * &BigUInt::from(limbs(3, 250));
+        let g = a.gcd(&b);
+        assert_eq!(&g % &common, BigUInt::from(0u64));
+        assert_eq!((&a / &g).gcd(&(&b / &g)), BigUInt::from(1u64));
+        check_extended(&a, &b);
+    }
+
+    #[test]
+    fn lcm_test() {
+        assert_eq!(
+            BigUInt::from(4u64).lcm(&BigUInt::from(6u64)),
+            BigUInt::from(12u64)
+        );
+        assert_eq!(
+            BigUInt::from(0u64).lcm(&BigUInt::from(6u64)),
+            BigUInt::from(0u64)
+        );
+        assert_eq!(BigInt::from(-4).lcm(&BigInt::from(6)), BigInt::from(12));
+
+        let a = BigUInt::from(limbs(4, 20));
+        let b = BigUInt::from(limbs(5, 30));
+        assert_eq!(&a.lcm(&b) * &a.gcd(&b), &a * &b);
+    }
+
+    #[test]
+    fn bigint_extended_gcd_test() {
+        for (a, b) in [
+            (240, 46),
+            (-240, 46),
+            (240, -46),
+            (-240, -46),
+            (0, -7),
+            (-7, 0),
+        ] {
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            let (g, x, y) = a.extended_gcd(&b);
+            assert_eq!(g, a.gcd(&b));
+            assert_eq!(&a * &x + &b * &y, g);
+        }
+        assert_eq!(BigInt::from(-240).gcd(&BigInt::from(46)), BigInt::from(2));
+    }
+}
@@ -1,102 +1,111 @@
+use super::limbs::Limbs;
 use super::BigUInt;
 
 pub fn mul_helper(lhs: u64, rhs: u64) -> BigUInt {
     match lhs.widening_mul(rhs) {
         (f, 0) => BigUInt::from(f),
-        (f, s) => BigUInt::from(vec![f, s]),
+        (f, s) => BigUInt(Limbs::from(&[f, s][..])),
     }
 }
 
-pub fn split_shl(value: u64, rhs: u32) -> (u64, u64) {
-    (
-        value.checked_shr(64 - rhs).unwrap_or(0),
-        value.checked_shl(rhs).unwrap_or(0),
-    )
+// drops leading zero limbs of a slice
+pub fn trim_slice(limbs: &[u64]) -> &[u64] {
+    let len = limbs
+        .iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |i| i + 1);
+    &limbs[..len]
 }
 
-pub fn shl_n64(value: BigUInt, shamt: usize) -> BigUInt {
-    let inner = value.0;
-    let mut new_inner = vec![0; shamt];
-    new_inner.extend(inner);
+// value += other * 2^(64 * shamt), in place
+pub fn add_shifted(value: &mut BigUInt, other: &[u64], shamt: usize) {
+    let other = trim_slice(other);
+    if other.is_empty() {
+        return;
+    }
+    if value.0.len() < shamt + other.len() {
+        value.0.resize(shamt + other.len(), 0);
+    }
+
+    let (head, tail) = value.0[shamt..].split_at_mut(other.len());
+    let mut carry = false;
+    for (a, &b) in head.iter_mut().zip(other) {
+        (*a, carry) = a.carrying_add(b, carry);
+    }
+    for a in tail {
+        if !carry {
+            break;
+        }
+        (*a, carry) = a.overflowing_add(1);
+    }
+    if carry {
+        value.0.push(1);
+    }
+}
 
-    BigUInt(new_inner)
+// acc = acc * mul + add, in place
+pub fn mul_add_limb(acc: &mut Limbs, mul: u64, add: u64) {
+    let mut carry = add;
+    for limb in acc.iter_mut() {
+        let tmp = *limb as u128 * mul as u128 + carry as u128;
+        *limb = tmp as u64;
+        carry = (tmp >> 64) as u64;
+    }
+    if carry != 0 {
+        acc.push(carry);
+    }
 }
 
-pub fn split_shr(value: u64, rhs: u32) -> (u64, u64) {
-    (
-        value.checked_shr(rhs).unwrap_or(0),
-        value.checked_shl(64 - rhs).unwrap_or(0),
-    )
+pub fn shl_n64(mut value: BigUInt, shamt: usize) -> BigUInt {
+    if !value.is_zero() && shamt != 0 {
+        value.0.insert_front(shamt, 0);
+    }
+
+    value
 }
 
-pub fn shr_n64(value: &BigUInt, shamt: usize) -> BigUInt {
-    BigUInt(value.0[shamt..].to_vec())
+pub fn shr_n64(mut value: BigUInt, shamt: usize) -> BigUInt {
+    let shamt = shamt.min(value.0.len());
+    value.0.remove_range(..shamt);
+
+    value
 }
 
-pub fn shr64(value: BigUInt, rhs: u32) -> BigUInt {
-    let mut iter = value
-        .0
-        .into_iter()
-        .map(|value| split_shr(value, rhs))
-        .flat_map(|(l, r)| [r, l]);
-
-    let _ = iter.next(); // remove lower bits
-
-    let mut buff = Vec::new();
-
-    loop {
-        match (iter.next(), iter.next()) {
-            (Some(a), Some(b)) => {
-                buff.push(a | b);
-            }
-            (Some(a), None) => {
-                buff.push(a);
-                break;
-            }
-            _ => unreachable!("iterator has even number of elements"),
-        }
+// shifts by less than 64 bits reusing the allocation
+pub fn shr64(mut value: BigUInt, rhs: u32) -> BigUInt {
+    if rhs == 0 {
+        return value;
     }
 
-    while buff.last() == Some(&0) {
-        let _ = buff.pop();
+    let mut carry = 0;
+    for limb in value.0.iter_mut().rev() {
+        let next = *limb << (64 - rhs);
+        *limb = (*limb >> rhs) | carry;
+        carry = next;
+    }
+    if value.0.last() == Some(&0) {
+        let _ = value.0.pop();
     }
 
-    buff.into()
+    value
 }
 
-pub fn shl64(value: BigUInt, rhs: u32) -> BigUInt {
-    let mut iter = value
-        .0
-        .into_iter()
-        .map(|value| split_shl(value, rhs))
-        .flat_map(|(r, l)| [l, r]);
-
-    let mut buff = Vec::new();
-
-    if let Some(a) = iter.next() {
-        buff.push(a);
-    } else {
-        return BigUInt::from(0);
-    }
-
-    loop {
-        match (iter.next(), iter.next()) {
-            (Some(a), Some(b)) => {
-                buff.push(a | b);
-            }
-            (Some(a), None) => {
-                buff.push(a);
-                break;
-            }
-            _ => unreachable!("iterator has even number of elements"),
-        }
+pub fn shl64(mut value: BigUInt, rhs: u32) -> BigUInt {
+    if rhs == 0 {
+        return value;
     }
 
-    while let Some(0) = buff.last() {
-        let _ = buff.pop();
+    let mut carry = 0;
+    for limb in value.0.iter_mut() {
+        let next = *limb >> (64 - rhs);
+        *limb = (*limb << rhs) | carry;
+        carry = next;
+    }
+    if carry != 0 {
+        value.0.push(carry);
     }
 
-    buff.into()
+    value
 }
 
 #[cfg(test)]
@@ -105,22 +114,22 @@ mod tests {
 
     #[test]
     fn shl64_test() {
-        assert_eq!(shl64(BigUInt::from(1), 0), BigUInt::from(1));
+        assert_eq!(shl64(BigUInt::from(1u64), 0), BigUInt::from(1u64));
         assert_eq!(
             shl64(BigUInt::from(vec![0, 0, 0, 3 << 62, 1 << 63]), 1),
             BigUInt::from(vec![0, 0, 0, 1 << 63, 1, 1])
         );
         assert_eq!(
-            shl64(BigUInt::from(0x123_0456_0789), 61),
+            shl64(BigUInt::from(0x123_0456_0789u64), 61),
             BigUInt::from(vec![0x2000_0000_0000_0000, 0x24_608A_C0F1]),
         );
     }
 
     #[test]
     fn shl_n64_test() {
-        assert_eq!(shr_n64(&BigUInt::from(1), 0), BigUInt::from(1));
+        assert_eq!(shr_n64(BigUInt::from(1u64), 0), BigUInt::from(1u64));
         assert_eq!(
-            shl_n64(BigUInt::from(123), 5),
+            shl_n64(BigUInt::from(123u64), 5),
             BigUInt::from(vec![0, 0, 0, 0, 0, 123])
         );
         assert_eq!(
@@ -136,9 +145,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_shifted_test() {
+        let mut num = BigUInt::from(vec![u64::MAX, u64::MAX, 7]);
+        add_shifted(&mut num, &[1, 0], 1);
+        assert_eq!(num, BigUInt::from(vec![u64::MAX, 0, 8]));
+
+        let mut num = BigUInt::from(0u64);
+        add_shifted(&mut num, &[5, 0], 2);
+        assert_eq!(num, BigUInt::from(vec![0, 0, 5]));
+
+        let mut num = BigUInt::from(vec![u64::MAX, u64::MAX]);
+        add_shifted(&mut num, &[1], 0);
+        assert_eq!(num, BigUInt::from(vec![0, 0, 1]));
+    }
+
     #[test]
     fn shr64_test() {
-        assert_eq!(shr64(BigUInt::from(1), 0), BigUInt::from(1));
+        assert_eq!(shr64(BigUInt::from(1u64), 0), BigUInt::from(1u64));
         assert_eq!(
             shr64(BigUInt::from(vec![0, 0, 0, 1 << 63, 1, 1]), 1),
             BigUInt::from(vec![0, 0, 0, 3 << 62, 1 << 63])
@@ -149,21 +173,21 @@ mod tests {
                 BigUInt::from(vec![0x2000_0000_0000_0000, 0x24_608A_C0F1]),
                 61
             ),
-            BigUInt::from(0x123_0456_0789)
+            BigUInt::from(0x123_0456_0789u64)
         );
     }
 
     #[test]
     fn shr_n64_test() {
-        assert_eq!(shr_n64(&BigUInt::from(1), 0), BigUInt::from(1));
+        assert_eq!(shr_n64(BigUInt::from(1u64), 0), BigUInt::from(1u64));
         assert_eq!(
-            shr_n64(&BigUInt::from(vec![0, 0, 0, 0, 0, 123]), 5),
-            BigUInt::from(123),
+            shr_n64(BigUInt::from(vec![0, 0, 0, 0, 0, 123]), 5),
+            BigUInt::from(123u64),
         );
 
         assert_eq!(
             shr_n64(
-                &BigUInt::from(vec![
+                BigUInt::from(vec![
                     0x0000_0000_0000_0000,
                     0x2000_0000_0000_0000,
                     0x24_608A_C0F1
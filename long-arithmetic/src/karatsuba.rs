@@ -1,47 +1,87 @@
-use crate::helpers::mul_helper;
+use crate::helpers::{add_shifted, mul_helper, trim_slice};
+use crate::mul::{map_parallel, mul, square};
 
-use super::{BigInt, BigUInt};
+use super::limbs::Limbs;
+use super::BigUInt;
 
-// splits vec into two
+// splits slice into two, leading zeros of the lower part are removed
 // example ([1, 2, 3, 4, 5, 6, 7], 4)-> ([1, 2, 3, 4], [5, 6, 7])
-fn split(mut vec: Vec<u64>, mut split: usize) -> (Vec<u64>, Vec<u64>) {
-    split = split.min(vec.len());
-    let right = vec[split..].to_vec();
-    vec.truncate(split);
+fn split(slice: &[u64], split: usize) -> (&[u64], &[u64]) {
+    let (lower, upper) = slice.split_at(split.min(slice.len()));
 
-    (vec, right)
+    (trim_slice(lower), upper)
+}
+
+fn sum(lhs: &[u64], rhs: &[u64]) -> BigUInt {
+    let mut sum = BigUInt(Limbs::with_capacity(lhs.len().max(rhs.len()) + 1));
+    add_shifted(&mut sum, lhs, 0);
+    add_shifted(&mut sum, rhs, 0);
+    sum
 }
 
 pub fn karatsuba_mul(rhs: BigUInt, lhs: BigUInt) -> BigUInt {
-    if let (Ok(rhs), Ok(lhs)) = (u64::try_from(&rhs), u64::try_from(&lhs)) {
-        return mul_helper(lhs, rhs);
-    }
+    karatsuba(&rhs.0, &lhs.0)
+}
 
-    let right = rhs.0;
-    let left = lhs.0;
+// operands must not have leading zeros
+pub(crate) fn karatsuba(rhs: &[u64], lhs: &[u64]) -> BigUInt {
+    if rhs.len() <= 1 && lhs.len() <= 1 {
+        let (rhs, lhs) = (rhs.first().copied(), lhs.first().copied());
+        return mul_helper(lhs.unwrap_or(0), rhs.unwrap_or(0));
+    }
 
-    let tmp = right.len().max(left.len());
+    let tmp = rhs.len().max(lhs.len());
     let split_point = tmp / 2 + tmp % 2;
 
-    let (right_lower, right_upper) = split(right, split_point);
-    let (left_lower, left_upper) = split(left, split_point);
-
-    let right_lower = BigUInt::from(right_lower);
-    let right_upper = BigUInt::from(right_upper);
-    let left_lower = BigUInt::from(left_lower);
-    let left_upper = BigUInt::from(left_upper);
-
-    let upper = BigInt::from(karatsuba_mul(right_upper.clone(), left_upper.clone()));
-    let lower = BigInt::from(karatsuba_mul(right_lower.clone(), left_lower.clone()));
-    let middle = BigInt::from(karatsuba_mul(
-        right_upper + right_lower,
-        left_upper + left_lower,
-    )) - upper.clone()
-        - lower.clone();
-
-    ((upper << (split_point * 128) as u32) + (middle << (split_point * 64) as u32) + lower)
-        .try_into()
-        .unwrap()
+    let (right_lower, right_upper) = split(rhs, split_point);
+    let (left_lower, left_upper) = split(lhs, split_point);
+
+    let (right_sum, left_sum) = (sum(right_upper, right_lower), sum(left_upper, left_lower));
+    let [upper, mut result, mut middle] = map_parallel(
+        rhs.len().min(lhs.len()),
+        [
+            (right_upper, left_upper),
+            (right_lower, left_lower),
+            (right_sum.0.as_slice(), left_sum.0.as_slice()),
+        ],
+        |(rhs, lhs)| mul(rhs, lhs),
+    );
+    // (ru + rl) * (lu + ll) - ru * lu - rl * ll = ru * ll + rl * lu is never negative
+    middle -= &upper;
+    middle -= &result;
+
+    result
+        .0
+        .reserve((rhs.len() + lhs.len()).saturating_sub(result.0.len()));
+    add_shifted(&mut result, &middle.0, split_point);
+    add_shifted(&mut result, &upper.0, 2 * split_point);
+    result
+}
+
+// (u * B + l)^2 = u^2 * B^2 + ((u + l)^2 - u^2 - l^2) * B + l^2 with three squarings
+pub(crate) fn karatsuba_square(limbs: &[u64]) -> BigUInt {
+    if limbs.len() <= 1 {
+        let limb = limbs.first().copied().unwrap_or(0);
+        return mul_helper(limb, limb);
+    }
+
+    let split_point = limbs.len().div_ceil(2);
+    let (lower, upper) = split(limbs, split_point);
+
+    let upper_sum = sum(upper, lower);
+    let [upper_square, mut result, mut middle] =
+        map_parallel(limbs.len(), [upper, lower, &upper_sum.0], |limbs| {
+            square(limbs)
+        });
+    middle -= &upper_square;
+    middle -= &result;
+
+    result
+        .0
+        .reserve((2 * limbs.len()).saturating_sub(result.0.len()));
+    add_shifted(&mut result, &middle.0, split_point);
+    add_shifted(&mut result, &upper_square.0, 2 * split_point);
+    result
 }
 
 #[cfg(test)]
@@ -51,17 +91,18 @@ mod tests {
     #[test]
     fn split_test() {
         assert_eq!(
-            split(vec![1, 2, 3, 4, 5, 6, 7], 4),
-            (vec![1, 2, 3, 4], vec![5, 6, 7])
+            split(&[1, 2, 3, 4, 5, 6, 7], 4),
+            (&[1, 2, 3, 4][..], &[5, 6, 7][..])
         );
         assert_eq!(
-            split(vec![1, 2, 3, 4, 5, 6, 7], 2),
-            (vec![1, 2], vec![3, 4, 5, 6, 7])
+            split(&[1, 2, 3, 4, 5, 6, 7], 2),
+            (&[1, 2][..], &[3, 4, 5, 6, 7][..])
         );
         assert_eq!(
-            split(vec![1, 2, 3, 4, 5, 6, 7], 10),
-            (vec![1, 2, 3, 4, 5, 6, 7], vec![])
+            split(&[1, 2, 3, 4, 5, 6, 7], 10),
+            (&[1, 2, 3, 4, 5, 6, 7][..], &[][..])
         );
+        assert_eq!(split(&[1, 0, 0, 4], 3), (&[1][..], &[4][..]));
     }
 
     const ORIGINAL: [u64; 1] = [0x123_0456_0789];
@@ -87,20 +128,20 @@ mod tests {
 
     #[test]
     fn karatsuba_test() {
-        let left = BigUInt::from(123);
-        let right = BigUInt::from(123);
+        let left = BigUInt::from(123u64);
+        let right = BigUInt::from(123u64);
         assert_eq!(karatsuba_mul(left.clone(), right.clone()), left * right);
 
-        let left = BigUInt::from(0);
-        let right = BigUInt::from(123_456_789);
+        let left = BigUInt::from(0u64);
+        let right = BigUInt::from(123_456_789u64);
         assert_eq!(karatsuba_mul(left.clone(), right.clone()), left * right);
 
-        let left = BigUInt::from(1);
-        let right = BigUInt::from(123_456_789);
+        let left = BigUInt::from(1u64);
+        let right = BigUInt::from(123_456_789u64);
         assert_eq!(karatsuba_mul(left.clone(), right.clone()), left * right);
 
-        let left = BigUInt::from(3);
-        let right = BigUInt::from(123_456_789);
+        let left = BigUInt::from(3u64);
+        let right = BigUInt::from(123_456_789u64);
         assert_eq!(karatsuba_mul(left.clone(), right.clone()), left * right);
 
         let left = BigUInt::from(u64::MAX);
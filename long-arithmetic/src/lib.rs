@@ -2,11 +2,41 @@
 
 pub mod bigint;
 pub mod biguint;
+mod bits;
 mod boilerplate;
+mod checked;
 mod cmp;
+pub mod combinatorics;
 mod convert;
+pub mod crt;
+pub mod ct;
+pub mod decimal;
+mod div;
+mod gcd;
 mod helpers;
 pub mod karatsuba;
+pub mod limbs;
+pub mod modular;
+pub mod mul;
+pub mod ntt;
+pub mod prime;
+mod radix;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod rational;
+mod root;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(test)]
+mod test_util;
+pub mod toom;
+#[cfg(feature = "num-traits")]
+mod traits;
+
+pub use checked::ArithmeticError;
+pub use convert::{ConversionError, ParseIntError};
+#[cfg(feature = "rand")]
+pub use random::RandBigInt;
 
 use bigint::*;
 use biguint::*;
@@ -0,0 +1,387 @@
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut, RangeBounds};
+
+// numbers up to this many limbs are stored without a heap allocation
+pub const INLINE_LIMBS: usize = 2;
+
+enum Storage {
+    Inline { len: u8, limbs: [u64; INLINE_LIMBS] },
+    Heap(Vec<u64>),
+}
+
+// Little-endian limbs of a `BigUInt`. Small values live inline and spill to the heap
+// when they grow; a heap buffer is kept when the value shrinks again, like the capacity
+// of a `Vec`, but clones of small values are inline again. Only the used limbs take
+// part in comparison and hashing
+pub struct Limbs(Storage);
+
+impl Limbs {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(Storage::Inline {
+            len: 0,
+            limbs: [0; INLINE_LIMBS],
+        })
+    }
+
+    // a single limb, usable in constants
+    #[must_use]
+    pub(crate) const fn single(limb: u64) -> Self {
+        let mut limbs = [0; INLINE_LIMBS];
+        limbs[0] = limb;
+        Self(Storage::Inline { len: 1, limbs })
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_LIMBS {
+            Self::new()
+        } else {
+            Self(Storage::Heap(Vec::with_capacity(capacity)))
+        }
+    }
+
+    // true if the limbs are stored inline
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Storage::Inline { .. })
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[u64] {
+        match &self.0 {
+            Storage::Inline { len, limbs } => &limbs[..*len as usize],
+            Storage::Heap(vec) => vec,
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u64] {
+        match &mut self.0 {
+            Storage::Inline { len, limbs } => &mut limbs[..*len as usize],
+            Storage::Heap(vec) => vec,
+        }
+    }
+
+    // the heap buffer, moving inline limbs into a new one with room for `additional` more
+    fn spill(&mut self, additional: usize) -> &mut Vec<u64> {
+        if let Storage::Inline { len, limbs } = self.0 {
+            let len = len as usize;
+            let mut vec = Vec::with_capacity((len + additional).max(2 * INLINE_LIMBS));
+            vec.extend_from_slice(&limbs[..len]);
+            self.0 = Storage::Heap(vec);
+        }
+        match &mut self.0 {
+            Storage::Heap(vec) => vec,
+            Storage::Inline { .. } => unreachable!(),
+        }
+    }
+
+    pub fn push(&mut self, limb: u64) {
+        match &mut self.0 {
+            Storage::Inline { len, limbs } if (*len as usize) < INLINE_LIMBS => {
+                limbs[*len as usize] = limb;
+                *len += 1;
+            }
+            _ => self.spill(1).push(limb),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<u64> {
+        match &mut self.0 {
+            Storage::Inline { len: 0, .. } => None,
+            Storage::Inline { len, limbs } => {
+                *len -= 1;
+                Some(limbs[*len as usize])
+            }
+            Storage::Heap(vec) => vec.pop(),
+        }
+    }
+
+    pub fn truncate(&mut self, new_len: usize) {
+        match &mut self.0 {
+            Storage::Inline { len, .. } if new_len < *len as usize => *len = new_len as u8,
+            Storage::Inline { .. } => {}
+            Storage::Heap(vec) => vec.truncate(new_len),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub fn resize(&mut self, new_len: usize, value: u64) {
+        match &mut self.0 {
+            Storage::Inline { len, limbs } if new_len <= INLINE_LIMBS => {
+                if new_len > *len as usize {
+                    limbs[*len as usize..new_len].fill(value);
+                }
+                *len = new_len as u8;
+            }
+            _ => {
+                let additional = new_len.saturating_sub(self.len());
+                self.spill(additional).resize(new_len, value);
+            }
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        match &mut self.0 {
+            Storage::Inline { len, .. } if *len as usize + additional <= INLINE_LIMBS => {}
+            _ => self.spill(additional).reserve(additional),
+        }
+    }
+
+    pub fn extend_from_slice(&mut self, other: &[u64]) {
+        if self.is_inline() && self.len() + other.len() <= INLINE_LIMBS {
+            for &limb in other {
+                self.push(limb);
+            }
+        } else {
+            self.spill(other.len()).extend_from_slice(other);
+        }
+    }
+
+    // inserts `count` copies of `value` at the front, i.e. shifts the number by whole limbs
+    pub fn insert_front(&mut self, count: usize, value: u64) {
+        let len = self.len();
+        self.resize(len + count, value);
+        self.as_mut_slice().copy_within(..len, count);
+        self.as_mut_slice()[..count].fill(value);
+    }
+
+    // removes the limbs in `range`
+    pub fn remove_range(&mut self, range: impl RangeBounds<usize>) {
+        match &mut self.0 {
+            Storage::Heap(vec) => {
+                vec.drain(range);
+            }
+            Storage::Inline { .. } => {
+                let mut vec = self.as_slice().to_vec();
+                vec.drain(range);
+                *self = Self::from(vec);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u64> {
+        match self.0 {
+            Storage::Inline { .. } => self.as_slice().to_vec(),
+            Storage::Heap(vec) => vec,
+        }
+    }
+}
+
+impl Default for Limbs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Limbs {
+    type Target = [u64];
+
+    fn deref(&self) -> &[u64] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for Limbs {
+    fn deref_mut(&mut self) -> &mut [u64] {
+        self.as_mut_slice()
+    }
+}
+
+impl Clone for Limbs {
+    fn clone(&self) -> Self {
+        Self::from(self.as_slice())
+    }
+}
+
+impl std::fmt::Debug for Limbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl PartialEq for Limbs {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Limbs {}
+
+impl PartialEq<Vec<u64>> for Limbs {
+    fn eq(&self, other: &Vec<u64>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Hash for Limbs {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl From<&[u64]> for Limbs {
+    fn from(value: &[u64]) -> Self {
+        if value.len() <= INLINE_LIMBS {
+            let mut limbs = [0; INLINE_LIMBS];
+            limbs[..value.len()].copy_from_slice(value);
+            Self(Storage::Inline {
+                len: value.len() as u8,
+                limbs,
+            })
+        } else {
+            Self(Storage::Heap(value.to_vec()))
+        }
+    }
+}
+
+// short vectors are moved inline and their buffer is freed
+impl From<Vec<u64>> for Limbs {
+    fn from(value: Vec<u64>) -> Self {
+        if value.len() <= INLINE_LIMBS {
+            Self::from(value.as_slice())
+        } else {
+            Self(Storage::Heap(value))
+        }
+    }
+}
+
+impl From<Limbs> for Vec<u64> {
+    fn from(value: Limbs) -> Self {
+        value.into_vec()
+    }
+}
+
+impl FromIterator<u64> for Limbs {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut limbs = Self::new();
+        limbs.extend(iter);
+        limbs
+    }
+}
+
+impl Extend<u64> for Limbs {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for limb in iter {
+            self.push(limb);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Limbs {
+    type Item = &'a u64;
+    type IntoIter = std::slice::Iter<'a, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Limbs {
+    type Item = &'a mut u64;
+    type IntoIter = std::slice::IterMut<'a, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_test() {
+        let mut limbs = Limbs::new();
+        assert!(limbs.is_inline() && limbs.is_empty());
+        limbs.push(1);
+        limbs.push(2);
+        assert!(limbs.is_inline());
+        assert_eq!(limbs, vec![1, 2]);
+
+        // spills on growth and keeps the buffer after shrinking
+        limbs.push(3);
+        assert!(!limbs.is_inline());
+        assert_eq!(limbs.pop(), Some(3));
+        assert!(!limbs.is_inline());
+        assert_eq!(limbs, Limbs::from(vec![1, 2]));
+        assert!(limbs.clone().is_inline());
+
+        limbs.resize(5, 9);
+        assert_eq!(limbs, vec![1, 2, 9, 9, 9]);
+        limbs.truncate(1);
+        assert_eq!(limbs.into_vec(), vec![1]);
+
+        let mut limbs = Limbs::from(vec![1, 2]);
+        limbs.truncate(256);
+        assert_eq!(limbs, vec![1, 2]);
+    }
+
+    #[test]
+    fn operations_test() {
+        let mut limbs = Limbs::from(vec![5]);
+        limbs.resize(2, 0);
+        assert!(limbs.is_inline());
+        assert_eq!(limbs, vec![5, 0]);
+        limbs.resize(0, 0);
+        assert!(limbs.is_empty() && limbs.pop().is_none());
+
+        let mut limbs = Limbs::from(vec![1, 2]);
+        limbs.insert_front(2, 0);
+        assert_eq!(limbs, vec![0, 0, 1, 2]);
+        limbs.remove_range(..3);
+        assert_eq!(limbs, vec![2]);
+
+        let mut limbs: Limbs = (1..=2).collect();
+        limbs.extend_from_slice(&[3]);
+        limbs.extend([4, 5]);
+        assert_eq!(limbs, vec![1, 2, 3, 4, 5]);
+        for limb in &mut limbs {
+            *limb *= 2;
+        }
+        assert_eq!(limbs.iter().sum::<u64>(), 30);
+
+        let mut limbs = Limbs::from(vec![7, 8]);
+        limbs.remove_range(1..);
+        assert!(limbs.is_inline());
+        assert_eq!(limbs, vec![7]);
+        limbs.reserve(1);
+        assert!(limbs.is_inline());
+        limbs.reserve(2);
+        assert!(!limbs.is_inline());
+        limbs.clear();
+        assert_eq!(limbs, Limbs::new());
+    }
+
+    #[test]
+    fn biguint_test() {
+        use crate::BigUInt;
+
+        // results of small operations don't allocate
+        let a = BigUInt::from(u64::MAX);
+        let b = BigUInt::from(3u64);
+        for value in [
+            &a * &b,
+            &a * &a,
+            &a + &b,
+            (&a * &b) / b.clone(),
+            "340282366920938463463374607431768211455".parse().unwrap(),
+            BigUInt::from(u128::MAX) >> 3u32,
+        ] {
+            assert!(value.0.is_inline(), "{value}");
+        }
+        let mut big = &(&a * &a) * &a;
+        assert!(!big.0.is_inline());
+        assert!((&big / &(&a * &a)).0.is_inline());
+        // `/=` keeps the buffer of the dividend
+        big /= &(&a * &a);
+        assert_eq!(big, a);
+        assert!(!big.0.is_inline());
+    }
+}
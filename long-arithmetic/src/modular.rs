@@ -0,0 +1,446 @@
+use crate::helpers::{shl_n64, shr_n64};
+use crate::ArithmeticError;
+
+use super::limbs::Limbs;
+use super::{BigInt, BigUInt, Sign};
+
+// Montgomery multiplication for a fixed odd modulus `n` of `k` limbs with R = 2^(64 * k).
+// Values are kept in Montgomery form `a * R mod n`, so a product needs one
+// multiplication and a word-by-word reduction instead of a full `div_rem`.
+#[derive(Clone, Debug)]
+pub struct MontgomeryContext {
+    modulus: BigUInt,
+    // -n^(-1) mod 2^64
+    n_inv: u64,
+    // R^2 mod n
+    r2: BigUInt,
+}
+
+impl MontgomeryContext {
+    // `new` without the panic for even moduli
+    pub fn try_new(modulus: BigUInt) -> Result<Self, ArithmeticError> {
+        if modulus.divisible_by_2() {
+            return Err(ArithmeticError::EvenModulus);
+        }
+        Ok(Self::new(modulus))
+    }
+
+    #[must_use]
+    pub fn new(modulus: BigUInt) -> Self {
+        assert!(
+            !modulus.divisible_by_2(),
+            "Montgomery reduction requires an odd modulus"
+        );
+
+        // Newton iteration doubles the number of correct bits: 1 -> 2 -> ... -> 64
+        let n0 = modulus.0[0];
+        let mut inv = 1u64;
+        for _ in 0..6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(n0.wrapping_mul(inv)));
+        }
+
+        let r2 = shl_n64(BigUInt::from(1u64), 2 * modulus.0.len()) % &modulus;
+
+        Self {
+            modulus,
+            n_inv: inv.wrapping_neg(),
+            r2,
+        }
+    }
+
+    #[must_use]
+    pub fn modulus(&self) -> &BigUInt {
+        &self.modulus
+    }
+
+    // t * R^(-1) mod n for t < n * R
+    fn redc(&self, mut t: Limbs) -> BigUInt {
+        let n = &self.modulus.0;
+        let k = n.len();
+        debug_assert!(t.len() <= 2 * k);
+        t.resize(2 * k + 1, 0);
+
+        for i in 0..k {
+            // makes t[i] zero by adding a multiple of n
+            let m = t[i].wrapping_mul(self.n_inv);
+            let mut carry = 0;
+            for (j, &limb) in n.iter().enumerate() {
+                let tmp = m as u128 * limb as u128 + t[i + j] as u128 + carry as u128;
+                t[i + j] = tmp as u64;
+                carry = (tmp >> 64) as u64;
+            }
+            for limb in &mut t[i + k..] {
+                if carry == 0 {
+                    break;
+                }
+                let overflow;
+                (*limb, overflow) = limb.overflowing_add(carry);
+                carry = u64::from(overflow);
+            }
+        }
+
+        let mut result = shr_n64(BigUInt(t), k);
+        while result.0.last() == Some(&0) {
+            let _ = result.0.pop();
+        }
+        if result >= self.modulus {
+            result -= &self.modulus;
+        }
+        result
+    }
+
+    // a * R mod n
+    #[must_use]
+    pub fn to_montgomery(&self, value: &BigUInt) -> BigUInt {
+        if *value < self.modulus {
+            self.redc((value * &self.r2).0)
+        } else {
+            self.redc((&(value % &self.modulus) * &self.r2).0)
+        }
+    }
+
+    #[must_use]
+    pub fn from_montgomery(&self, value: &BigUInt) -> BigUInt {
+        if *value < self.modulus {
+            self.redc(value.0.clone())
+        } else {
+            self.redc((value % &self.modulus).0)
+        }
+    }
+
+    // product of two values in Montgomery form, the result is in Montgomery form too
+    #[must_use]
+    pub fn mul(&self, lhs: &BigUInt, rhs: &BigUInt) -> BigUInt {
+        if *lhs < self.modulus && *rhs < self.modulus {
+            self.redc((lhs * rhs).0)
+        } else {
+            self.mul(&(lhs % &self.modulus), &(rhs % &self.modulus))
+        }
+    }
+
+    #[must_use]
+    pub fn modmul(&self, lhs: &BigUInt, rhs: &BigUInt) -> BigUInt {
+        let product = self.mul(&self.to_montgomery(lhs), &self.to_montgomery(rhs));
+        self.from_montgomery(&product)
+    }
+
+    #[must_use]
+    pub fn modpow(&self, base: &BigUInt, exp: &BigUInt) -> BigUInt {
+        let one = self.to_montgomery(&BigUInt::from(1u64));
+        let base = self.to_montgomery(base);
+        let power = pow_window(one, &base, exp, |lhs, rhs| self.mul(lhs, rhs));
+        self.from_montgomery(&power)
+    }
+}
+
+// Barrett reduction for a fixed modulus `n` of `k` limbs: with mu = b^(2k) / n
+// the quotient of x < b^(2k) is estimated by two multiplications and a shift.
+// Unlike `MontgomeryContext` the modulus doesn't have to be odd.
+#[derive(Clone, Debug)]
+pub struct BarrettContext {
+    modulus: BigUInt,
+    mu: BigUInt,
+}
+
+impl BarrettContext {
+    // `new` without the panic for a zero modulus
+    pub fn try_new(modulus: BigUInt) -> Result<Self, ArithmeticError> {
+        if modulus.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self::new(modulus))
+    }
+
+    #[must_use]
+    pub fn new(modulus: BigUInt) -> Self {
+        assert!(!modulus.is_zero(), "Division by zero");
+
+        let mu = shl_n64(BigUInt::from(1u64), 2 * modulus.0.len()) / &modulus;
+        Self { modulus, mu }
+    }
+
+    #[must_use]
+    pub fn modulus(&self) -> &BigUInt {
+        &self.modulus
+    }
+
+    #[must_use]
+    pub fn reduce(&self, value: &BigUInt) -> BigUInt {
+        let k = self.modulus.0.len();
+        if *value < self.modulus {
+            return value.clone();
+        }
+        if value.0.len() > 2 * k {
+            return value % &self.modulus;
+        }
+
+        // estimated quotient is at most two less than the real one
+        let high = BigUInt::from(value.0[k - 1..].to_vec());
+        let quotient = shr_n64(&high * &self.mu, k + 1);
+        let mut rem = value - &(&quotient * &self.modulus);
+        while rem >= self.modulus {
+            rem -= &self.modulus;
+        }
+        rem
+    }
+
+    #[must_use]
+    pub fn modmul(&self, lhs: &BigUInt, rhs: &BigUInt) -> BigUInt {
+        self.reduce(&(&self.reduce(lhs) * &self.reduce(rhs)))
+    }
+
+    #[must_use]
+    pub fn modpow(&self, base: &BigUInt, exp: &BigUInt) -> BigUInt {
+        let one = self.reduce(&BigUInt::from(1u64));
+        let base = self.reduce(base);
+        pow_window(one, &base, exp, |lhs, rhs| self.reduce(&(lhs * rhs)))
+    }
+}
+
+// left-to-right exponentiation with a fixed 4-bit window
+fn pow_window(
+    one: BigUInt,
+    base: &BigUInt,
+    exp: &BigUInt,
+    mul: impl Fn(&BigUInt, &BigUInt) -> BigUInt,
+) -> BigUInt {
+    let mut table = vec![one.clone(), base.clone()];
+    for i in 2..16 {
+        table.push(mul(&table[i - 1], base));
+    }
+
+    let mut acc = one;
+    for limb in exp.0.iter().rev() {
+        for shift in (0..16).rev() {
+            for _ in 0..4 {
+                acc = mul(&acc, &acc);
+            }
+            let window = (limb >> (4 * shift)) & 0xF;
+            if window != 0 {
+                acc = mul(&acc, &table[window as usize]);
+            }
+        }
+    }
+    acc
+}
+
+impl BigUInt {
+    #[must_use]
+    pub fn modmul(&self, rhs: &Self, modulus: &Self) -> Self {
+        &(self * rhs) % modulus
+    }
+
+    // Montgomery exponentiation for odd moduli, Barrett reduction otherwise
+    #[must_use]
+    pub fn modpow(&self, exp: &Self, modulus: &Self) -> Self {
+        assert!(!modulus.is_zero(), "Division by zero");
+
+        if modulus.divisible_by_2() {
+            BarrettContext::new(modulus.clone()).modpow(self, exp)
+        } else {
+            MontgomeryContext::new(modulus.clone()).modpow(self, exp)
+        }
+    }
+
+    // x such that self * x = 1 (mod modulus), if it exists
+    #[must_use]
+    pub fn modinv(&self, modulus: &Self) -> Option<Self> {
+        assert!(!modulus.is_zero(), "Division by zero");
+
+        let (g, x, _) = (self % modulus).extended_gcd(modulus);
+        if g != 1u64 {
+            return None;
+        }
+
+        let (_, x) = x.div_rem_ref(&BigInt::from(modulus.clone()));
+        match x.sign {
+            Sign::Plus => Some(x.inner),
+            Sign::Minus => Some(modulus - x.inner),
+        }
+    }
+}
+
+// modular arithmetic is defined for non-negative `BigInt`s only
+fn non_negative(value: &BigInt) -> &BigUInt {
+    assert!(
+        value.sign == Sign::Plus,
+        "modular arithmetic requires non-negative operands"
+    );
+    &value.inner
+}
+
+impl BigInt {
+    #[must_use]
+    pub fn modmul(&self, rhs: &Self, modulus: &Self) -> Self {
+        let modulus = non_negative(modulus);
+        Self::from(non_negative(self).modmul(non_negative(rhs), modulus))
+    }
+
+    #[must_use]
+    pub fn modpow(&self, exp: &Self, modulus: &Self) -> Self {
+        let modulus = non_negative(modulus);
+        Self::from(non_negative(self).modpow(non_negative(exp), modulus))
+    }
+
+    // inverse of `self` modulo a positive `modulus`, `self` may be negative
+    #[must_use]
+    pub fn modinv(&self, modulus: &Self) -> Option<Self> {
+        let modulus = non_negative(modulus);
+        let inv = self.inner.modinv(modulus)?;
+        match self.sign {
+            Sign::Minus if !inv.is_zero() => Some(Self::from(modulus - inv)),
+            _ => Some(Self::from(inv)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::biguint::Pow;
+    use crate::test_util::limbs;
+
+    fn modpow_u128(mut base: u128, mut exp: u128, modulus: u128) -> u128 {
+        let mut acc = 1 % modulus;
+        base %= modulus;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base % modulus;
+            }
+            base = base * base % modulus;
+            exp >>= 1;
+        }
+        acc
+    }
+
+    #[test]
+    fn modpow_test() {
+        for (base, exp, modulus) in [
+            (4u64, 13u64, 497u64),
+            (0, 0, 7),
+            (5, 0, 1),
+            (123_456_789, 987_654_321, 1_000_000_007),
+            (123_456_789, 987_654_321, 1 << 32),
+            (u32::MAX as u64, u64::MAX, 4_294_967_291),
+        ] {
+            let expected = modpow_u128(base as u128, exp as u128, modulus as u128);
+            let result = BigUInt::from(base).modpow(&BigUInt::from(exp), &BigUInt::from(modulus));
+            assert_eq!(
+                result,
+                BigUInt::from(expected as u64),
+                "{base}^{exp} mod {modulus}"
+            );
+        }
+
+        // Fermat's little theorem for the Mersenne prime 2^521 - 1
+        let p = (BigUInt::from(1u64) << 521) - 1;
+        let base = BigUInt::from(limbs(7, 8));
+        assert_eq!(base.modpow(&(p.clone() - 1), &p), BigUInt::from(1u64));
+
+        // agrees with `pow` followed by `%`
+        let base = BigUInt::from(limbs(3, 3));
+        let modulus = BigUInt::from(limbs(5, 2));
+        assert_eq!(
+            base.modpow(&BigUInt::from(37u64), &modulus),
+            base.clone().pow(37) % &modulus
+        );
+        let even = &modulus << 3;
+        assert_eq!(
+            base.modpow(&BigUInt::from(37u64), &even),
+            base.pow(37) % &even
+        );
+    }
+
+    #[test]
+    fn montgomery_test() {
+        let mut modulus = limbs(11, 5);
+        modulus[0] |= 1;
+        let modulus = BigUInt::from(modulus);
+        let context = MontgomeryContext::new(modulus.clone());
+        let a = BigUInt::from(limbs(12, 5)) % &modulus;
+        let b = BigUInt::from(limbs(13, 7));
+
+        let a_mont = context.to_montgomery(&a);
+        assert_eq!(context.from_montgomery(&a_mont), a);
+        assert_eq!(context.modmul(&a, &b), a.modmul(&b, &modulus));
+
+        // operands that aren't reduced
+        let context = MontgomeryContext::new(BigUInt::from(1_000_000_007u64));
+        let value = (BigUInt::from(1u64) << 200) + 5;
+        let plain = context.from_montgomery(&value);
+        assert_eq!(context.to_montgomery(&plain), value % context.modulus());
+
+        let max = BigUInt::from(u64::MAX);
+        let product = context.mul(&max, &max);
+        assert!(product < *context.modulus());
+        assert_eq!(
+            context.from_montgomery(&product),
+            context.modmul(
+                &context.from_montgomery(&max),
+                &context.from_montgomery(&max)
+            )
+        );
+    }
+
+    #[test]
+    fn barrett_test() {
+        let modulus = BigUInt::from(limbs(21, 4)) << 5;
+        let context = BarrettContext::new(modulus.clone());
+        for len in [0, 1, 3, 4, 6, 8, 9, 13] {
+            let value = BigUInt::from(limbs(len as u64 + 1, len));
+            assert_eq!(context.reduce(&value), &value % &modulus);
+        }
+
+        let a = BigUInt::from(limbs(22, 4));
+        let b = BigUInt::from(limbs(23, 6));
+        assert_eq!(context.modmul(&a, &b), a.modmul(&b, &modulus));
+    }
+
+    #[test]
+    fn modinv_test() {
+        let modulus = BigUInt::from(1_000_000_007u64);
+        assert_eq!(
+            BigUInt::from(123_456_789u64).modinv(&modulus),
+            Some(BigUInt::from(18_633_540u64))
+        );
+        assert_eq!(BigUInt::from(6u64).modinv(&BigUInt::from(9u64)), None);
+        assert_eq!(BigUInt::from(0u64).modinv(&BigUInt::from(9u64)), None);
+        assert_eq!(
+            BigUInt::from(5u64).modinv(&BigUInt::from(1u64)),
+            Some(BigUInt::from(0u64))
+        );
+
+        let modulus = BigInt::from(1_000_000_007);
+        assert_eq!(
+            BigInt::from(-123_456_789).modinv(&modulus),
+            Some(BigInt::from(1_000_000_007 - 18_633_540))
+        );
+
+        let mut modulus = limbs(6, 60);
+        modulus[0] |= 1;
+        let modulus = BigUInt::from(modulus);
+        let value = BigUInt::from(limbs(7, 90));
+        if let Some(inv) = value.modinv(&modulus) {
+            assert_eq!(value.modmul(&inv, &modulus), BigUInt::from(1u64));
+        }
+    }
+
+    #[test]
+    fn bigint_test() {
+        let modulus = BigInt::from(1_000_000_007);
+        assert_eq!(
+            BigInt::from(4).modpow(&BigInt::from(13), &BigInt::from(497)),
+            BigInt::from(445)
+        );
+        assert_eq!(
+            BigInt::from(123_456_789).modmul(&BigInt::from(987_654_321), &modulus),
+            BigInt::from(259_106_859)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "non-negative")]
+    fn bigint_negative_test() {
+        let _ = BigInt::from(-4).modpow(&BigInt::from(13), &BigInt::from(497));
+    }
+}
@@ -0,0 +1,282 @@
+use super::helpers::{add_shifted, trim_slice};
+use super::karatsuba::{karatsuba, karatsuba_square};
+use super::limbs::Limbs;
+use super::ntt::mul_slices;
+use super::toom::{toom3, toom3_square};
+use super::BigUInt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Algorithm selection for `BigUInt` multiplication.
+// The choice is made by the length (in limbs) of the shorter operand:
+// below `karatsuba` schoolbook multiplication is used, then Karatsuba
+// up to `toom3`, Toom-3 up to `ntt` and the number-theoretic transform above it.
+// With the `parallel` feature, the independent sub-products of operands of at least
+// `parallel` limbs are computed on the rayon thread pool; the result doesn't depend on it.
+// Thresholds are global and can be tuned at runtime, see `benches/mul.rs`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MulThresholds {
+    pub karatsuba: usize,
+    pub toom3: usize,
+    pub ntt: usize,
+    pub parallel: usize,
+}
+
+// recursive methods need some minimal size to make progress
+const KARATSUBA_MIN: usize = 4;
+const TOOM3_MIN: usize = 9;
+
+static KARATSUBA_THRESHOLD: AtomicUsize = AtomicUsize::new(MulThresholds::DEFAULT.karatsuba);
+static TOOM3_THRESHOLD: AtomicUsize = AtomicUsize::new(MulThresholds::DEFAULT.toom3);
+static NTT_THRESHOLD: AtomicUsize = AtomicUsize::new(MulThresholds::DEFAULT.ntt);
+static PARALLEL_THRESHOLD: AtomicUsize = AtomicUsize::new(MulThresholds::DEFAULT.parallel);
+
+impl MulThresholds {
+    // crossovers measured with `benches/mul.rs`, which runs up to 65536 limbs, on one
+    // core: NTT overtakes Toom-3 between 24576 and 32768 limbs. `parallel` makes every
+    // task take at least tens of microseconds
+    pub const DEFAULT: Self = Self {
+        karatsuba: 32,
+        toom3: 128,
+        ntt: 32768,
+        parallel: 1024,
+    };
+
+    #[must_use]
+    pub fn get() -> Self {
+        Self {
+            karatsuba: KARATSUBA_THRESHOLD.load(Ordering::Relaxed),
+            toom3: TOOM3_THRESHOLD.load(Ordering::Relaxed),
+            ntt: NTT_THRESHOLD.load(Ordering::Relaxed),
+            parallel: PARALLEL_THRESHOLD.load(Ordering::Relaxed),
+        }
+    }
+
+    // sets thresholds for all subsequent multiplications
+    pub fn set(self) {
+        KARATSUBA_THRESHOLD.store(self.karatsuba, Ordering::Relaxed);
+        TOOM3_THRESHOLD.store(self.toom3, Ordering::Relaxed);
+        NTT_THRESHOLD.store(self.ntt, Ordering::Relaxed);
+        PARALLEL_THRESHOLD.store(self.parallel, Ordering::Relaxed);
+    }
+}
+
+impl Default for MulThresholds {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// `items.map(f)`, with the items processed in parallel when `size`, the length of the
+// shorter operand of the multiplication being split, is at least `MulThresholds::parallel`
+pub(crate) fn map_parallel<T: Send, R: Send, const N: usize>(
+    size: usize,
+    items: [T; N],
+    f: impl Fn(T) -> R + Send + Sync,
+) -> [R; N] {
+    #[cfg(feature = "parallel")]
+    if size >= PARALLEL_THRESHOLD.load(Ordering::Relaxed) {
+        use rayon::prelude::*;
+        let results: Vec<R> = items.into_par_iter().map(f).collect();
+        return results
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("the length is preserved"));
+    }
+
+    let _ = size;
+    items.map(f)
+}
+
+// quadratic multiplication
+pub fn schoolbook_mul(rhs: BigUInt, lhs: BigUInt) -> BigUInt {
+    schoolbook(&rhs.0, &lhs.0)
+}
+
+// acc += src * factor for slices of the same length, returns the carry out of the last limb
+fn addmul_limb(acc: &mut [u64], src: &[u64], factor: u64) -> u64 {
+    let mut carry = 0;
+    for (a, &s) in acc.iter_mut().zip(src) {
+        let tmp = s as u128 * factor as u128 + *a as u128 + carry as u128;
+        *a = tmp as u64;
+        carry = (tmp >> 64) as u64;
+    }
+    carry
+}
+
+fn trimmed(mut limbs: Limbs) -> BigUInt {
+    while limbs.last() == Some(&0) {
+        let _ = limbs.pop();
+    }
+    BigUInt(limbs)
+}
+
+// zeroed buffer, inline for small products
+fn zeros(len: usize) -> Limbs {
+    let mut limbs = Limbs::with_capacity(len);
+    limbs.resize(len, 0);
+    limbs
+}
+
+// row by row multiply-accumulate into a single buffer
+fn schoolbook(rhs: &[u64], lhs: &[u64]) -> BigUInt {
+    let mut out = zeros(rhs.len() + lhs.len());
+    for (i, &limb) in rhs.iter().enumerate() {
+        out[i + lhs.len()] = addmul_limb(&mut out[i..i + lhs.len()], lhs, limb);
+    }
+    trimmed(out)
+}
+
+// every product x[i] * x[j] with i != j appears twice in a square,
+// so it is computed once, doubled and the squares x[i]^2 are added on top
+fn schoolbook_square(limbs: &[u64]) -> BigUInt {
+    let n = limbs.len();
+    let mut out = zeros(2 * n);
+    for (i, &limb) in limbs.iter().enumerate() {
+        out[i + n] = addmul_limb(&mut out[2 * i + 1..i + n], &limbs[i + 1..], limb);
+    }
+
+    let mut carry = 0;
+    for limb in &mut out {
+        (*limb, carry) = ((*limb << 1) | carry, *limb >> 63);
+    }
+
+    let mut carry = false;
+    for (i, &limb) in limbs.iter().enumerate() {
+        let (low, high) = limb.widening_mul(limb);
+        (out[2 * i], carry) = out[2 * i].carrying_add(low, carry);
+        (out[2 * i + 1], carry) = out[2 * i + 1].carrying_add(high, carry);
+    }
+    debug_assert!(!carry);
+
+    trimmed(out)
+}
+
+// multiplies numbers with very different lengths by slicing the longer one
+// into pieces of the shorter one's length
+fn unbalanced_mul(long: &[u64], short: &[u64]) -> BigUInt {
+    let chunk = short.len();
+    let mut result = BigUInt(Limbs::with_capacity(long.len() + short.len()));
+
+    for (i, piece) in long.chunks(chunk).enumerate() {
+        add_shifted(&mut result, &mul(trim_slice(piece), short).0, i * chunk);
+    }
+
+    result
+}
+
+// multiplication with the algorithm chosen by operands' sizes,
+// operands must not have leading zeros
+pub(crate) fn mul(rhs: &[u64], lhs: &[u64]) -> BigUInt {
+    let thresholds = MulThresholds::get();
+    let (short, long) = if rhs.len() <= lhs.len() {
+        (rhs, lhs)
+    } else {
+        (lhs, rhs)
+    };
+    let size = short.len();
+
+    if size < thresholds.karatsuba.max(KARATSUBA_MIN) {
+        schoolbook(short, long)
+    } else if long.len() >= 2 * size {
+        unbalanced_mul(long, short)
+    } else if size < thresholds.toom3.max(TOOM3_MIN) {
+        karatsuba(short, long)
+    } else if size < thresholds.ntt {
+        toom3(short, long)
+    } else {
+        BigUInt::from(mul_slices(short, long))
+    }
+}
+
+// square with the algorithm chosen by operand's size, the same thresholds apply
+pub(crate) fn square(limbs: &[u64]) -> BigUInt {
+    let thresholds = MulThresholds::get();
+    let size = limbs.len();
+
+    if size < thresholds.karatsuba.max(KARATSUBA_MIN) {
+        schoolbook_square(limbs)
+    } else if size < thresholds.toom3.max(TOOM3_MIN) {
+        karatsuba_square(limbs)
+    } else if size < thresholds.ntt {
+        toom3_square(limbs)
+    } else {
+        BigUInt::from(mul_slices(limbs, limbs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::*;
+
+    #[test]
+    fn unbalanced_mul_test() {
+        let long = BigUInt::from(vec![u64::MAX; 37]);
+        let short = BigUInt::from(vec![0, 5, u64::MAX, 0, 1]);
+        assert_eq!(
+            unbalanced_mul(&long.0, &short.0),
+            schoolbook_mul(long, short)
+        );
+    }
+
+    #[test]
+    fn mul_test() {
+        let left = (1..=300u64).map(|x| x.wrapping_mul(0x0123_4567_89AB_CDEF));
+        let left = BigUInt::from(left.collect_vec());
+        let right = BigUInt::from((1..=200).map(|x| u64::MAX / x).collect_vec());
+
+        let expected = schoolbook_mul(left.clone(), right.clone());
+        assert_eq!(mul(&left.0, &right.0), expected);
+        assert_eq!(mul(&right.0, &left.0), expected);
+    }
+
+    #[test]
+    fn schoolbook_test() {
+        let max = BigUInt::from(vec![u64::MAX; 5]);
+        let expected = (BigUInt::from(1u64) << 640) - (BigUInt::from(1u64) << 321) + 1;
+        assert_eq!(schoolbook(&max.0, &max.0), expected);
+        assert_eq!(schoolbook_square(&max.0), expected);
+        assert_eq!(schoolbook(&[], &max.0), BigUInt::from(0u64));
+        assert_eq!(schoolbook_square(&[]), BigUInt::from(0u64));
+    }
+
+    #[test]
+    fn square_test() {
+        for len in [1, 2, 3, 7, 8, 15, 16, 17, 40, 101, 300] {
+            let value = (1..=len as u64).map(|x| x.wrapping_mul(0xFEDC_BA98_7654_3211));
+            let value = BigUInt::from(value.collect_vec());
+            let expected = schoolbook(&value.0, &value.0);
+
+            assert_eq!(schoolbook_square(&value.0), expected, "{len} limbs");
+            assert_eq!(karatsuba_square(&value.0), expected, "{len} limbs");
+            assert_eq!(toom3_square(&value.0), expected, "{len} limbs");
+            assert_eq!(square(&value.0), expected, "{len} limbs");
+        }
+    }
+
+    #[test]
+    fn parallel_test() {
+        assert_eq!(
+            map_parallel(usize::MAX, [1, 2, 3], |x| x * 10),
+            [10, 20, 30]
+        );
+
+        // above the threshold the sub-products may run in parallel, the results must match
+        // the sequential schoolbook products exactly
+        let len = MulThresholds::DEFAULT.parallel + 7;
+        let left = BigUInt::from(
+            (1..=len as u64)
+                .map(|x| x.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+                .collect_vec(),
+        );
+        let right = BigUInt::from((1..=len as u64).map(|x| u64::MAX / x).collect_vec());
+        let expected = schoolbook(&left.0, &right.0);
+        let expected_square = schoolbook_square(&left.0);
+
+        assert_eq!(karatsuba(&left.0, &right.0), expected);
+        assert_eq!(toom3(&left.0, &right.0), expected);
+        assert_eq!(BigUInt::from(mul_slices(&left.0, &right.0)), expected);
+        assert_eq!(karatsuba_square(&left.0), expected_square);
+        assert_eq!(toom3_square(&left.0), expected_square);
+        assert_eq!(BigUInt::from(mul_slices(&left.0, &left.0)), expected_square);
+    }
+}
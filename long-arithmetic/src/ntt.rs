@@ -0,0 +1,346 @@
+use super::mul::map_parallel;
+use super::BigUInt;
+
+// Number-theoretic transform multiplication.
+// Limbs are convolved modulo three ~62-bit primes and the exact coefficients
+// are recovered with CRT (Garner's algorithm). The primes' product is ~2^184,
+// while a coefficient of the convolution is bounded by min(n, m) * 2^128,
+// so every limb can be used as a transform coefficient as is.
+
+// prime modulus with precomputed Montgomery constants (R = 2^64)
+#[derive(Clone, Copy)]
+struct Prime {
+    p: u64,
+    // -p^-1 mod 2^64
+    p_inv: u64,
+    // R^2 mod p
+    r2: u64,
+    // primitive root
+    g: u64,
+    // largest power of two dividing p - 1
+    max_log: u32,
+}
+
+impl Prime {
+    const fn new(p: u64, g: u64) -> Self {
+        let mut inv = p;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+            i += 1;
+        }
+        let r = ((1u128 << 64) % p as u128) as u64;
+        let r2 = ((r as u128 * r as u128) % p as u128) as u64;
+
+        Self {
+            p,
+            p_inv: inv.wrapping_neg(),
+            r2,
+            g,
+            max_log: (p - 1).trailing_zeros(),
+        }
+    }
+
+    fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.p_inv);
+        let r = ((t + m as u128 * self.p as u128) >> 64) as u64;
+        if r >= self.p {
+            r - self.p
+        } else {
+            r
+        }
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        let s = a + b;
+        if s >= self.p {
+            s - self.p
+        } else {
+            s
+        }
+    }
+
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + self.p - b
+        }
+    }
+
+    fn mont(&self, a: u64) -> u64 {
+        self.mul(a % self.p, self.r2)
+    }
+
+    fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut acc = self.mont(1);
+        while exp > 0 {
+            if exp % 2 == 1 {
+                acc = self.mul(acc, base);
+            }
+            base = self.mul(base, base);
+            exp /= 2;
+        }
+        acc
+    }
+
+    // powers of a primitive n-th root of unity (or its inverse), w^0 .. w^(n/2 - 1)
+    fn twiddles(&self, log_n: u32, inverse: bool) -> Vec<u64> {
+        let n = 1u64 << log_n;
+        let mut exp = (self.p - 1) >> log_n;
+        if inverse {
+            exp = (self.p - 1) - exp;
+        }
+        let w = self.pow(self.mont(self.g), exp);
+
+        let mut table = Vec::with_capacity((n / 2) as usize);
+        let mut cur = self.mont(1);
+        for _ in 0..n / 2 {
+            table.push(cur);
+            cur = self.mul(cur, w);
+        }
+        table
+    }
+
+    // decimation in frequency: natural order in, bit-reversed order out
+    fn forward(&self, a: &mut [u64], table: &[u64]) {
+        let n = a.len();
+        let mut len = n;
+        while len >= 2 {
+            let half = len / 2;
+            let stride = n / len;
+            for block in a.chunks_exact_mut(len) {
+                let (lo, hi) = block.split_at_mut(half);
+                for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let (x, y) = (*u, *v);
+                    *u = self.add(x, y);
+                    *v = self.mul(self.sub(x, y), table[j * stride]);
+                }
+            }
+            len = half;
+        }
+    }
+
+    // decimation in time: bit-reversed order in, natural order out (unscaled)
+    fn inverse(&self, a: &mut [u64], table: &[u64]) {
+        let n = a.len();
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let stride = n / len;
+            for block in a.chunks_exact_mut(len) {
+                let (lo, hi) = block.split_at_mut(half);
+                for (j, (u, v)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let x = *u;
+                    let y = self.mul(*v, table[j * stride]);
+                    *u = self.add(x, y);
+                    *v = self.sub(x, y);
+                }
+            }
+            len *= 2;
+        }
+    }
+
+    // cyclic convolution of `lhs` and `rhs` modulo p, in plain (non-Montgomery) form
+    fn convolve(&self, lhs: &[u64], rhs: &[u64], log_n: u32) -> Vec<u64> {
+        let n = 1usize << log_n;
+        let forward = self.twiddles(log_n, false);
+        let inverse = self.twiddles(log_n, true);
+
+        let load = |limbs: &[u64]| {
+            let mut buff: Vec<u64> = limbs.iter().map(|&x| self.mont(x)).collect();
+            buff.resize(n, 0);
+            buff
+        };
+
+        let transform = |limbs: &[u64]| {
+            let mut buff = load(limbs);
+            self.forward(&mut buff, &forward);
+            buff
+        };
+
+        let mut a;
+        if std::ptr::eq(lhs, rhs) {
+            a = transform(lhs);
+            a.iter_mut().for_each(|x| *x = self.mul(*x, *x));
+        } else {
+            let b;
+            [a, b] = map_parallel(lhs.len().min(rhs.len()), [lhs, rhs], transform);
+            a.iter_mut().zip(b).for_each(|(x, y)| *x = self.mul(*x, y));
+        }
+
+        self.inverse(&mut a, &inverse);
+
+        // multiplying a Montgomery value by a plain one leaves the plain product
+        let n_inv = self.pow(self.mont(n as u64), self.p - 2);
+        let n_inv = self.reduce(n_inv as u128);
+        a.iter_mut().for_each(|x| *x = self.mul(*x, n_inv));
+        a
+    }
+}
+
+const P1: Prime = Prime::new(0x3A00_0000_0000_0001, 3); // 29 * 2^57 + 1
+const P2: Prime = Prime::new(0x2280_0000_0000_0001, 5); // 69 * 2^55 + 1
+const P3: Prime = Prime::new(0x1B00_0000_0000_0001, 5); // 27 * 2^56 + 1
+
+// modular inverse of `a` modulo prime `m` in Montgomery form of `m`
+fn inv_mont(m: &Prime, a: u64) -> u64 {
+    m.pow(m.mont(a), m.p - 2)
+}
+
+// Garner's recombination of (r1, r2, r3) into a 192-bit value [lo, mid, hi]
+struct Crt {
+    // p1^-1 mod p2, Montgomery form
+    p1_inv_p2: u64,
+    // (p1 * p2)^-1 mod p3, Montgomery form
+    p12_inv_p3: u64,
+    // p1 mod p3, Montgomery form
+    p1_p3: u64,
+    p12: u128,
+}
+
+impl Crt {
+    fn new() -> Self {
+        let p12 = P1.p as u128 * P2.p as u128;
+        Self {
+            p1_inv_p2: inv_mont(&P2, P1.p),
+            p12_inv_p3: inv_mont(&P3, (p12 % P3.p as u128) as u64),
+            p1_p3: P3.mont(P1.p),
+            p12,
+        }
+    }
+
+    fn combine(&self, r1: u64, r2: u64, r3: u64) -> [u64; 3] {
+        let x1 = r1;
+        let x2 = P2.mul(P2.sub(r2, x1 % P2.p), self.p1_inv_p2);
+        let t = P3.sub(r3, x1 % P3.p);
+        let t = P3.sub(t, P3.mul(x2, self.p1_p3));
+        let x3 = P3.mul(t, self.p12_inv_p3);
+
+        // x1 + p1 * x2 < p1 * p2 < 2^124
+        let low = x1 as u128 + P1.p as u128 * x2 as u128;
+        let lo_part = (self.p12 as u64) as u128 * x3 as u128;
+        let hi_part = (self.p12 >> 64) * x3 as u128;
+
+        let (sum, carry) = low.overflowing_add(lo_part);
+        let top = hi_part + (sum >> 64) + ((carry as u128) << 64);
+        [sum as u64, top as u64, (top >> 64) as u64]
+    }
+}
+
+// product of two limb slices, leading zeros are trimmed
+pub(crate) fn mul_slices(lhs: &[u64], rhs: &[u64]) -> Vec<u64> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return vec![];
+    }
+
+    let out_len = lhs.len() + rhs.len();
+    let log_n = (out_len - 1).next_power_of_two().trailing_zeros();
+    assert!(
+        log_n <= P1.max_log.min(P2.max_log).min(P3.max_log),
+        "Operands are too large for NTT multiplication"
+    );
+
+    let rhs = if lhs == rhs { lhs } else { rhs };
+    let [c1, c2, c3] = map_parallel(lhs.len().min(rhs.len()), [P1, P2, P3], |prime| {
+        prime.convolve(lhs, rhs, log_n)
+    });
+
+    let crt = Crt::new();
+    let mut result = Vec::with_capacity(out_len);
+    let mut carry = [0u64; 3];
+
+    for i in 0..out_len - 1 {
+        let value = crt.combine(c1[i], c2[i], c3[i]);
+
+        let (w0, c) = value[0].overflowing_add(carry[0]);
+        let (w1, c) = value[1].carrying_add(carry[1], c);
+        let (w2, _) = value[2].carrying_add(carry[2], c);
+
+        result.push(w0);
+        carry = [w1, w2, 0];
+    }
+    result.extend(carry.into_iter().take(out_len - result.len()));
+
+    while result.last() == Some(&0) {
+        let _ = result.pop();
+    }
+
+    result
+}
+
+pub fn ntt_mul(lhs: BigUInt, rhs: BigUInt) -> BigUInt {
+    BigUInt::from(mul_slices(&lhs.0, &rhs.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::limbs;
+
+    #[test]
+    fn prime_test() {
+        for prime in [P1, P2, P3] {
+            let one = prime.mont(1);
+            let x = prime.mont(123_456_789);
+            assert_eq!(prime.reduce(prime.mul(x, one) as u128), 123_456_789);
+            assert_eq!(prime.pow(prime.mont(prime.g), prime.p - 1), one);
+            assert_ne!(prime.pow(prime.mont(prime.g), (prime.p - 1) / 2), one);
+        }
+    }
+
+    #[test]
+    fn crt_test() {
+        let crt = Crt::new();
+        let value: u128 = 0x1234_5678_9ABC_DEF0_0FED_CBA9_8765_4321;
+        let [lo, mid, hi] = crt.combine(
+            (value % P1.p as u128) as u64,
+            (value % P2.p as u128) as u64,
+            (value % P3.p as u128) as u64,
+        );
+        assert_eq!((lo, mid, hi), (value as u64, (value >> 64) as u64, 0));
+    }
+
+    #[test]
+    fn ntt_mul_test() {
+        assert_eq!(
+            ntt_mul(BigUInt::from(0u64), BigUInt::from(123u64)),
+            BigUInt::from(0u64)
+        );
+        assert_eq!(
+            ntt_mul(BigUInt::from(u64::MAX), BigUInt::from(u64::MAX)),
+            BigUInt::from(u64::MAX) * BigUInt::from(u64::MAX)
+        );
+
+        let left = BigUInt::from(vec![0xFFFF_FFFF_FFFF_FFFF, 0x1111_1111_1111_1111, 0x3333]);
+        let right = BigUInt::from(vec![0xFFFF_FFFF_FFFF_FFFF, 0x2222_2222_2222_2222, 0x3456]);
+        assert_eq!(ntt_mul(left.clone(), right.clone()), left * right);
+
+        for (l, r) in [
+            (1, 1),
+            (2, 3),
+            (17, 17),
+            (64, 1),
+            (1, 129),
+            (100, 37),
+            (256, 255),
+        ] {
+            let left = BigUInt::from(limbs(l as u64 + 1, l));
+            let right = BigUInt::from(limbs(r as u64 + 1000, r));
+            assert_eq!(ntt_mul(left.clone(), right.clone()), left * right);
+        }
+    }
+
+    #[test]
+    fn ntt_mul_max_limbs_test() {
+        // worst case for coefficient size
+        let left = BigUInt::from(vec![u64::MAX; 150]);
+        let right = BigUInt::from(vec![u64::MAX; 90]);
+        assert_eq!(ntt_mul(left.clone(), right.clone()), left.clone() * right);
+        assert_eq!(ntt_mul(left.clone(), left.clone()), left.clone() * left);
+    }
+}
@@ -0,0 +1,434 @@
+use crate::div::rem_limb;
+use crate::modular::MontgomeryContext;
+
+use super::BigUInt;
+
+const SMALL_PRIMES_NUM: usize = 168;
+
+// all primes below 1000
+const SMALL_PRIMES: [u64; SMALL_PRIMES_NUM] = small_primes();
+
+const fn small_primes() -> [u64; SMALL_PRIMES_NUM] {
+    let mut primes = [0; SMALL_PRIMES_NUM];
+    let (mut count, mut n) = (0, 2);
+    while count < SMALL_PRIMES_NUM {
+        let mut i = 0;
+        while i < count && n % primes[i] != 0 {
+            i += 1;
+        }
+        if i == count {
+            primes[count] = n;
+            count += 1;
+        }
+        n += 1;
+    }
+    primes
+}
+
+// Some(answer) if trial division by small primes is enough to decide
+fn trial_division(n: &BigUInt) -> Option<bool> {
+    if let Ok(small) = u64::try_from(n) {
+        if small < 2 {
+            return Some(false);
+        }
+        for p in SMALL_PRIMES {
+            if p * p > small {
+                return Some(true);
+            }
+            if small.is_multiple_of(p) {
+                return Some(small == p);
+            }
+        }
+        return None;
+    }
+
+    // products of several small primes fit into a limb,
+    // so one pass over `n` checks a whole group of them
+    let mut start = 0;
+    while start < SMALL_PRIMES_NUM {
+        let mut end = start;
+        let mut product = 1u64;
+        while let Some(next) = SMALL_PRIMES.get(end).and_then(|&p| product.checked_mul(p)) {
+            product = next;
+            end += 1;
+        }
+
+        let rem = rem_limb(&n.0, product);
+        if SMALL_PRIMES[start..end]
+            .iter()
+            .any(|&p| rem.is_multiple_of(p))
+        {
+            return Some(false);
+        }
+        start = end;
+    }
+    None
+}
+
+// Miller-Rabin test for an odd n > 3 with n - 1 = d * 2^s
+struct MillerRabin {
+    context: MontgomeryContext,
+    d: BigUInt,
+    s: u32,
+    // 1 and n - 1 in Montgomery form
+    one: BigUInt,
+    minus_one: BigUInt,
+}
+
+impl MillerRabin {
+    fn new(n: &BigUInt) -> Self {
+        let n_minus_1 = n.clone() - 1;
+        let s = n_minus_1.trailing_zeros().unwrap() as u32;
+        let context = MontgomeryContext::new(n.clone());
+
+        Self {
+            d: &n_minus_1 >> s,
+            s,
+            one: context.to_montgomery(&BigUInt::from(1u64)),
+            minus_one: context.to_montgomery(&n_minus_1),
+            context,
+        }
+    }
+
+    // false if `base` is a witness of compositeness
+    fn test(&self, base: &BigUInt) -> bool {
+        let mut x = self
+            .context
+            .to_montgomery(&self.context.modpow(base, &self.d));
+        if x == self.one || x == self.minus_one {
+            return true;
+        }
+
+        for _ in 1..self.s {
+            x = self.context.mul(&x, &x);
+            if x == self.minus_one {
+                return true;
+            }
+            if x == self.one {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+// Jacobi symbol (a / n) for odd n
+fn jacobi_u64(mut a: u64, mut n: u64) -> i32 {
+    let mut result = 1;
+    a %= n;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+// Jacobi symbol (d / n) for odd n, reduced to single limbs by quadratic reciprocity
+fn jacobi(d: i64, n: &BigUInt) -> i32 {
+    let n_mod_8 = n.0[0] % 8;
+    let mut result = 1;
+
+    // (-1 / n)
+    if d < 0 && n_mod_8 % 4 == 3 {
+        result = -result;
+    }
+    // (2 / n)
+    let mut a = d.unsigned_abs();
+    let twos = a.trailing_zeros();
+    a >>= twos;
+    if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+        result = -result;
+    }
+    // (a / n) = (n / a) * (-1)^((a - 1) / 2 * (n - 1) / 2)
+    if a % 4 == 3 && n_mod_8 % 4 == 3 {
+        result = -result;
+    }
+
+    result * jacobi_u64(rem_limb(&n.0, a), a)
+}
+
+fn is_square(n: &BigUInt) -> bool {
+    let root = n.sqrt();
+    &root * &root == *n
+}
+
+// strong Lucas probable prime test with Selfridge's parameters, for odd n > 3
+fn strong_lucas(n: &BigUInt) -> bool {
+    // first D of 5, -7, 9, -11, ... with (D / n) = -1
+    let mut d: i64 = 5;
+    loop {
+        match jacobi(d, n) {
+            -1 => break,
+            0 if *n != d.unsigned_abs() => return false,
+            _ => {}
+        }
+        // there is no such D for squares
+        if d == -15 && is_square(n) {
+            return false;
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+
+    // P = 1, Q = (1 - D) / 4, n + 1 = k * 2^s
+    let context = MontgomeryContext::new(n.clone());
+    let residue = |value: i64| {
+        let abs = BigUInt::from(value.unsigned_abs()) % n;
+        let residue = if value < 0 && !abs.is_zero() {
+            n - abs
+        } else {
+            abs
+        };
+        context.to_montgomery(&residue)
+    };
+    let add = |lhs: &BigUInt, rhs: &BigUInt| {
+        let sum = lhs + rhs;
+        if sum >= *n {
+            sum - n
+        } else {
+            sum
+        }
+    };
+    let sub = |lhs: &BigUInt, rhs: &BigUInt| {
+        if lhs >= rhs {
+            lhs - rhs
+        } else {
+            lhs + n - rhs
+        }
+    };
+    // division by 2 commutes with the Montgomery form
+    let half = |value: BigUInt| {
+        if value.divisible_by_2() {
+            value >> 1
+        } else {
+            (value + n) >> 1
+        }
+    };
+
+    let d_mont = residue(d);
+    let q_mont = residue((1 - d) / 4);
+    let n_plus_1 = n.clone() + 1;
+    let s = n_plus_1.trailing_zeros().unwrap() as u32;
+    let k = &n_plus_1 >> s;
+
+    // U_1 = 1, V_1 = P = 1, Q^1
+    let mut u = residue(1);
+    let mut v = u.clone();
+    let mut q_k = q_mont.clone();
+    for i in (0..k.bits_num() - 1).rev() {
+        // U_2k = U_k * V_k, V_2k = V_k^2 - 2 * Q^k
+        u = context.mul(&u, &v);
+        v = sub(&context.mul(&v, &v), &add(&q_k, &q_k));
+        q_k = context.mul(&q_k, &q_k);
+        if k.bit(u64::from(i)) {
+            // U_(k+1) = (P * U_k + V_k) / 2, V_(k+1) = (D * U_k + P * V_k) / 2
+            let du = context.mul(&d_mont, &u);
+            u = half(add(&u, &v));
+            v = half(add(&du, &v));
+            q_k = context.mul(&q_k, &q_mont);
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = sub(&context.mul(&v, &v), &add(&q_k, &q_k));
+        if v.is_zero() {
+            return true;
+        }
+        q_k = context.mul(&q_k, &q_k);
+    }
+    false
+}
+
+impl BigUInt {
+    // Baillie-PSW test (Miller-Rabin to base 2 and a strong Lucas test),
+    // followed by `rounds` Miller-Rabin tests with pseudo-random bases.
+    // No composite number passing Baillie-PSW is known.
+    #[must_use]
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        if let Some(answer) = trial_division(self) {
+            return answer;
+        }
+
+        let miller_rabin = MillerRabin::new(self);
+        if !miller_rabin.test(&BigUInt::from(2u64)) || !strong_lucas(self) {
+            return false;
+        }
+
+        // bases are derived from `self`, so the result is deterministic
+        let mut seed = (self.0[0] ^ self.0.len() as u64) | 1;
+        let range = self.clone() - 3;
+        (0..rounds).all(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let base = BigUInt::from(seed) % &range + 2;
+            miller_rabin.test(&base)
+        })
+    }
+
+    // the smallest prime greater than `self`, by Baillie-PSW
+    #[must_use]
+    pub fn next_prime(&self) -> Self {
+        if *self < 2u64 {
+            return BigUInt::from(2u64);
+        }
+
+        let mut candidate = self.clone() + 1;
+        if candidate.divisible_by_2() && candidate != 2u64 {
+            candidate += 1;
+        }
+        while !candidate.is_probable_prime(0) {
+            candidate += 2;
+        }
+        candidate
+    }
+}
+
+// Miller-Rabin rounds on top of Baillie-PSW for generated primes
+#[cfg(feature = "rand")]
+const RANDOM_PRIME_ROUNDS: usize = 8;
+
+// random prime of exactly `bits` bits
+#[cfg(feature = "rand")]
+pub fn random_prime<R: rand::Rng + ?Sized>(bits: u32, rng: &mut R) -> BigUInt {
+    assert!(bits >= 2, "there are no primes with less than 2 bits");
+
+    let limbs = bits.div_ceil(64) as usize;
+    let top = (bits - 1) % 64;
+    loop {
+        let mut candidate: Vec<u64> = (0..limbs).map(|_| rng.gen()).collect();
+        candidate[limbs - 1] &= u64::MAX >> (63 - top);
+        candidate[limbs - 1] |= 1 << top;
+        candidate[0] |= 1;
+
+        let candidate = BigUInt::from(candidate);
+        if candidate.is_probable_prime(RANDOM_PRIME_ROUNDS) {
+            return candidate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_primes_test() {
+        assert_eq!(SMALL_PRIMES[..10], [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(SMALL_PRIMES[SMALL_PRIMES_NUM - 1], 997);
+    }
+
+    #[test]
+    fn jacobi_test() {
+        // (a / n) agrees with Euler's criterion for primes
+        for n in [3u64, 5, 7, 11, 13, 1_000_000_007] {
+            for a in [-11i64, -7, -1, 1, 2, 5, 6, 9, 10] {
+                let residue = a.rem_euclid(n as i64) as u64;
+                let euler =
+                    BigUInt::from(residue).modpow(&BigUInt::from((n - 1) / 2), &BigUInt::from(n));
+                let expected = match u64::try_from(&euler) {
+                    Ok(0) => 0,
+                    Ok(1) => 1,
+                    _ => -1,
+                };
+                assert_eq!(jacobi(a, &BigUInt::from(n)), expected, "({a} / {n})");
+            }
+        }
+        assert_eq!(jacobi(5, &BigUInt::from(21u64)), 1);
+        assert_eq!(jacobi(-7, &BigUInt::from(15u64)), 1);
+    }
+
+    #[test]
+    fn pseudoprime_test() {
+        // strong pseudoprimes to base 2
+        for n in [2047u64, 3_215_031_751, 3_825_123_056_546_413_051] {
+            let n = BigUInt::from(n);
+            assert!(MillerRabin::new(&n).test(&BigUInt::from(2u64)));
+            assert!(!n.is_probable_prime(0));
+        }
+        // strong Lucas pseudoprimes
+        for n in [5459u64, 5777, 10877, 16109, 18971] {
+            let n = BigUInt::from(n);
+            assert!(strong_lucas(&n));
+            assert!(!n.is_probable_prime(0));
+        }
+        // squares have no suitable D
+        assert!(!strong_lucas(&BigUInt::from(1_000_003u64 * 1_000_003)));
+    }
+
+    #[test]
+    fn is_probable_prime_test() {
+        let primes: Vec<u64> = (0..2000)
+            .filter(|&n| BigUInt::from(n).is_probable_prime(0))
+            .collect();
+        assert_eq!(primes.len(), 303);
+        assert!(primes.iter().all(|&p| (2..p).all(|d| p % d != 0)));
+
+        // Carmichael numbers
+        for n in [561u64, 41041, 825_265, 321_197_185, 5_394_826_801] {
+            assert!(!BigUInt::from(n).is_probable_prime(4));
+        }
+
+        // Mersenne numbers 2^p - 1
+        for (p, prime) in [
+            (61, true),
+            (67, false),
+            (89, true),
+            (127, true),
+            (521, true),
+            (523, false),
+        ] {
+            let n = (BigUInt::from(1u64) << p) - 1;
+            assert_eq!(n.is_probable_prime(4), prime, "2^{p} - 1");
+        }
+
+        // product of two large primes
+        let p = (BigUInt::from(1u64) << 127) - 1;
+        let q = (BigUInt::from(1u64) << 89) - 1;
+        assert!(!(&p * &q).is_probable_prime(0));
+        assert!(!(&p * &p).is_probable_prime(0));
+    }
+
+    #[test]
+    fn next_prime_test() {
+        assert_eq!(BigUInt::from(0u64).next_prime(), BigUInt::from(2u64));
+        assert_eq!(BigUInt::from(2u64).next_prime(), BigUInt::from(3u64));
+        assert_eq!(BigUInt::from(13u64).next_prime(), BigUInt::from(17u64));
+        assert_eq!(
+            BigUInt::from(1_000_000_000_000_000_000u64).next_prime(),
+            BigUInt::from(1_000_000_000_000_000_003u64)
+        );
+        assert_eq!(
+            (BigUInt::from(1u64) << 64).next_prime(),
+            BigUInt::from(vec![13, 1])
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_prime_test() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for bits in [2, 17, 64, 65, 256] {
+            let prime = random_prime(bits, &mut rng);
+            assert_eq!(prime.bits_num(), bits);
+            assert!(prime.is_probable_prime(8));
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use super::limbs::Limbs;
+use super::BigUInt;
+use crate::helpers::mul_add_limb;
+
+// Conversion between limbs and digits in radix 2..=36.
+// Power of two radixes are converted bit by bit in linear time.
+// Other radixes are processed in chunks - the largest power of radix fitting
+// into u64 (19 digits for radix 10). Long numbers are split in halves by powers
+// chunk^(2^k), which are computed once per conversion, so conversion cost
+// follows multiplication and division cost.
+
+// numbers up to this length (in limbs) are converted chunk by chunk
+const DIVIDE_AND_CONQUER_THRESHOLD: usize = 32;
+
+#[derive(Clone, Copy)]
+struct Chunk {
+    radix: u64,
+    // number of digits in one chunk
+    digits: usize,
+    // radix^digits
+    base: u64,
+}
+
+impl Chunk {
+    fn new(radix: u32) -> Self {
+        let radix = u64::from(radix);
+        let (mut digits, mut base) = (1, radix);
+        while let Some(next) = base.checked_mul(radix) {
+            base = next;
+            digits += 1;
+        }
+
+        Self {
+            radix,
+            digits,
+            base,
+        }
+    }
+
+    // [base, base^2, base^4, ...] until the power is longer than a half of `limbs`
+    fn powers(&self, limbs: usize) -> Vec<BigUInt> {
+        let mut powers = vec![BigUInt::from(self.base)];
+        loop {
+            let last = powers.last().unwrap();
+            if last.0.len() * 2 > limbs {
+                break powers;
+            }
+            let next = last * last;
+            powers.push(next);
+        }
+    }
+
+    fn parse(&self, digits: &[u8]) -> u64 {
+        digits
+            .iter()
+            .fold(0, |acc, &d| acc * self.radix + u64::from(d))
+    }
+
+    // digits of `value` left padded with zeros to `width`
+    fn format(&self, mut value: u64, width: usize, out: &mut Vec<u8>) {
+        let start = out.len();
+        while value != 0 {
+            out.push((value % self.radix) as u8);
+            value /= self.radix;
+        }
+        while out.len() - start < width {
+            out.push(0);
+        }
+        out[start..].reverse();
+    }
+}
+
+// quadratic parsing, chunk by chunk from the most significant digits
+fn parse_small(digits: &[u8], chunk: Chunk) -> BigUInt {
+    let first = match digits.len() % chunk.digits {
+        0 => chunk.digits.min(digits.len()),
+        n => n,
+    };
+    let (head, tail) = digits.split_at(first);
+
+    let mut acc = Limbs::new();
+    mul_add_limb(&mut acc, 0, chunk.parse(head));
+    for part in tail.chunks(chunk.digits) {
+        mul_add_limb(&mut acc, chunk.base, chunk.parse(part));
+    }
+
+    BigUInt(acc)
+}
+
+fn parse_recursive(digits: &[u8], chunk: Chunk, powers: &[BigUInt]) -> BigUInt {
+    if digits.len() <= DIVIDE_AND_CONQUER_THRESHOLD * chunk.digits || powers.is_empty() {
+        return parse_small(digits, chunk);
+    }
+
+    // largest power with less digits than the number
+    let k = (0..powers.len())
+        .rev()
+        .find(|&k| chunk.digits << k < digits.len())
+        .unwrap_or(0);
+    let (high, low) = digits.split_at(digits.len() - (chunk.digits << k));
+
+    let high = parse_recursive(high, chunk, powers);
+    let low = parse_recursive(low, chunk, &powers[..k]);
+    if high.is_zero() {
+        low
+    } else {
+        high * &powers[k] + low
+    }
+}
+
+fn parse_pow2(digits: &[u8], bits: u32) -> BigUInt {
+    let mut limbs = vec![0u64; (digits.len() * bits as usize).div_ceil(64)];
+
+    for (i, &d) in digits.iter().rev().enumerate() {
+        let pos = i * bits as usize;
+        let (limb, offset) = (pos / 64, (pos % 64) as u32);
+        limbs[limb] |= u64::from(d) << offset;
+        if offset + bits > 64 {
+            limbs[limb + 1] |= u64::from(d) >> (64 - offset);
+        }
+    }
+
+    while limbs.last() == Some(&0) {
+        let _ = limbs.pop();
+    }
+
+    BigUInt::from(limbs)
+}
+
+// builds a number from digit values (each less than `radix`), most significant first
+pub(crate) fn parse_radix(digits: &[u8], radix: u32) -> BigUInt {
+    if radix.is_power_of_two() {
+        return parse_pow2(digits, radix.trailing_zeros());
+    }
+
+    let chunk = Chunk::new(radix);
+    // one chunk takes a bit less than one limb
+    let limbs = digits.len() / chunk.digits + 1;
+    if limbs <= DIVIDE_AND_CONQUER_THRESHOLD {
+        parse_small(digits, chunk)
+    } else {
+        parse_recursive(digits, chunk, &chunk.powers(limbs / 2 + 1))
+    }
+}
+
+fn format_small(value: BigUInt, width: usize, chunk: Chunk, out: &mut Vec<u8>) {
+    let mut parts = vec![];
+    let mut num = value;
+    while !num.is_zero() {
+        let (div, rem) = num.div_rem_u64(chunk.base);
+        parts.push(rem);
+        num = div;
+    }
+
+    let mut digits = vec![];
+    if let Some(first) = parts.pop() {
+        chunk.format(first, 0, &mut digits);
+    }
+    for part in parts.into_iter().rev() {
+        chunk.format(part, chunk.digits, &mut digits);
+    }
+
+    out.extend(std::iter::repeat_n(0, width.saturating_sub(digits.len())));
+    out.extend(digits);
+}
+
+fn format_recursive(
+    value: BigUInt,
+    width: usize,
+    chunk: Chunk,
+    powers: &[BigUInt],
+    out: &mut Vec<u8>,
+) {
+    // largest power not longer than a half of the number
+    let k = (0..powers.len())
+        .rev()
+        .find(|&k| powers[k].0.len() * 2 <= value.0.len() + 1);
+
+    match k {
+        Some(k) if value.0.len() > DIVIDE_AND_CONQUER_THRESHOLD => {
+            let low_width = chunk.digits << k;
+            let (high, low) = crate::div::div_rem(&value, &powers[k]);
+            format_recursive(high, width.saturating_sub(low_width), chunk, powers, out);
+            format_recursive(low, low_width, chunk, &powers[..k], out);
+        }
+        _ => format_small(value, width, chunk, out),
+    }
+}
+
+fn format_pow2(value: &BigUInt, bits: u32) -> Vec<u8> {
+    let total = value.bits_num() as usize;
+    let mask = (1u64 << bits) - 1;
+
+    (0..total.div_ceil(bits as usize))
+        .rev()
+        .map(|i| {
+            let pos = i * bits as usize;
+            let (limb, offset) = (pos / 64, (pos % 64) as u32);
+            let mut d = value.0[limb] >> offset;
+            if offset + bits > 64 {
+                d |= value.0.get(limb + 1).unwrap_or(&0) << (64 - offset);
+            }
+            (d & mask) as u8
+        })
+        .collect()
+}
+
+// digit values of `value` without leading zeros, most significant first
+fn digits_radix(value: &BigUInt, radix: u32) -> Vec<u8> {
+    if value.is_zero() {
+        return vec![0];
+    }
+    if radix.is_power_of_two() {
+        return format_pow2(value, radix.trailing_zeros());
+    }
+
+    let chunk = Chunk::new(radix);
+    let mut out = vec![];
+    if value.0.len() <= DIVIDE_AND_CONQUER_THRESHOLD {
+        format_small(value.clone(), 0, chunk, &mut out);
+    } else {
+        let powers = chunk.powers(value.0.len() / 2 + 1);
+        format_recursive(value.clone(), 0, chunk, &powers, &mut out);
+    }
+    out
+}
+
+// representation in `radix` with lowercase letters
+pub(crate) fn format_radix(value: &BigUInt, radix: u32) -> String {
+    digits_radix(value, radix)
+        .into_iter()
+        .map(|d| char::from_digit(u32::from(d), radix).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::limbs;
+
+    fn digits(s: &str) -> Vec<u8> {
+        s.chars().map(|ch| ch.to_digit(36).unwrap() as u8).collect()
+    }
+
+    #[test]
+    fn chunk_test() {
+        let chunk = Chunk::new(10);
+        assert_eq!((chunk.digits, chunk.base), (19, 10_000_000_000_000_000_000));
+        let chunk = Chunk::new(36);
+        assert_eq!((chunk.digits, chunk.base), (12, 36u64.pow(12)));
+
+        let powers = Chunk::new(10).powers(4);
+        assert_eq!(powers.len(), 3);
+        assert_eq!(powers[0], BigUInt::from(10_000_000_000_000_000_000u64));
+        assert_eq!(powers[1], powers[0].clone() * powers[0].clone());
+        assert_eq!(powers[2], powers[1].clone() * powers[1].clone());
+    }
+
+    #[test]
+    fn parse_radix_test() {
+        assert_eq!(parse_radix(&digits("0"), 10), BigUInt::from(0u64));
+        assert_eq!(parse_radix(&digits("0000"), 10), BigUInt::from(0u64));
+        assert_eq!(parse_radix(&digits("0000"), 16), BigUInt::from(0u64));
+        assert_eq!(
+            parse_radix(&digits("18446744073709551616"), 10),
+            BigUInt::from(vec![0, 1])
+        );
+        assert_eq!(
+            parse_radix(&digits("12345678910111213141516"), 10),
+            BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D])
+        );
+        assert_eq!(
+            parse_radix(&digits("29d42b65689328bbe0c"), 16),
+            BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D])
+        );
+        assert_eq!(
+            parse_radix(&digits("2000000000000000000000"), 8),
+            BigUInt::from(vec![0, 1])
+        );
+        assert_eq!(parse_radix(&digits("zz"), 36), BigUInt::from(1295u64));
+    }
+
+    #[test]
+    fn format_radix_test() {
+        assert_eq!(format_radix(&BigUInt::from(0u64), 10), "0");
+        assert_eq!(format_radix(&BigUInt::from(0u64), 2), "0");
+        assert_eq!(
+            format_radix(&BigUInt::from(vec![0, 1]), 10),
+            "18446744073709551616"
+        );
+        assert_eq!(
+            format_radix(&BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]), 10),
+            "12345678910111213141516"
+        );
+        assert_eq!(
+            format_radix(&BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]), 16),
+            "29d42b65689328bbe0c"
+        );
+        assert_eq!(
+            format_radix(&BigUInt::from(vec![0, 1]), 8),
+            "2000000000000000000000"
+        );
+        assert_eq!(format_radix(&BigUInt::from(1295u64), 36), "zz");
+        assert_eq!(format_radix(&BigUInt::from(5u64), 2), "101");
+    }
+
+    #[test]
+    fn roundtrip_test() {
+        for radix in 2..=36 {
+            for len in [1, 3, 40] {
+                let value = BigUInt::from(limbs(len as u64 + u64::from(radix), len));
+                let digits = digits_radix(&value, radix);
+                assert_eq!(parse_radix(&digits, radix), value);
+            }
+
+            let value = BigUInt::from(0x0123_4567_89AB_CDEFu64);
+            assert_eq!(
+                u64::from_str_radix(&format_radix(&value, radix), radix),
+                Ok(0x0123_4567_89AB_CDEF)
+            );
+        }
+    }
+
+    #[test]
+    fn divide_and_conquer_test() {
+        let chunk = Chunk::new(10);
+        for len in [33, 64, 100, 257] {
+            let value = BigUInt::from(limbs(len as u64, len));
+
+            let mut expected = vec![];
+            format_small(value.clone(), 0, chunk, &mut expected);
+            let digits = digits_radix(&value, 10);
+            assert_eq!(digits, expected);
+
+            assert_eq!(parse_small(&digits, chunk), value);
+            assert_eq!(parse_radix(&digits, 10), value);
+        }
+
+        // zero limbs in the middle produce padded chunks
+        let mut inner = vec![0; 80];
+        inner[0] = 7;
+        inner[79] = 1;
+        let value = BigUInt::from(inner);
+        assert_eq!(parse_radix(&digits_radix(&value, 10), 10), value);
+        assert_eq!(parse_radix(&digits_radix(&value, 7), 7), value);
+
+        let s = format!("1{}", "0".repeat(1000));
+        assert_eq!(format_radix(&parse_radix(&digits(&s), 10), 10), s);
+    }
+}
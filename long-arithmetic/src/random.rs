@@ -0,0 +1,128 @@
+use rand::Rng;
+
+use super::{BigInt, BigUInt, Sign};
+
+// uniformly distributed big numbers from any `rand::Rng`
+pub trait RandBigInt {
+    // uniform in [0, 2^bits)
+    fn gen_biguint(&mut self, bits: u64) -> BigUInt;
+
+    // uniform in [0, bound), panics if `bound` is zero
+    fn gen_biguint_below(&mut self, bound: &BigUInt) -> BigUInt;
+
+    // uniform in [low, high), panics if the range is empty
+    fn gen_biguint_range(&mut self, low: &BigUInt, high: &BigUInt) -> BigUInt;
+
+    // uniform in (-2^bits, 2^bits)
+    fn gen_bigint(&mut self, bits: u64) -> BigInt;
+}
+
+impl<R: Rng + ?Sized> RandBigInt for R {
+    fn gen_biguint(&mut self, bits: u64) -> BigUInt {
+        let mut limbs: Vec<u64> = (0..bits.div_ceil(64)).map(|_| self.gen()).collect();
+        if let (Some(last), rest @ 1..) = (limbs.last_mut(), bits % 64) {
+            *last &= (1 << rest) - 1;
+        }
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUInt::from(limbs)
+    }
+
+    fn gen_biguint_below(&mut self, bound: &BigUInt) -> BigUInt {
+        assert!(!bound.is_zero(), "cannot sample from an empty range");
+
+        // rejection sampling, a candidate is accepted with probability above 1/2
+        let bits = bound.bit_length();
+        loop {
+            let candidate = self.gen_biguint(bits);
+            if candidate < *bound {
+                return candidate;
+            }
+        }
+    }
+
+    fn gen_biguint_range(&mut self, low: &BigUInt, high: &BigUInt) -> BigUInt {
+        assert!(low < high, "cannot sample from an empty range");
+        low + &self.gen_biguint_below(&(high - low))
+    }
+
+    fn gen_bigint(&mut self, bits: u64) -> BigInt {
+        loop {
+            let magnitude = self.gen_biguint(bits);
+            let sign = if self.gen() { Sign::Plus } else { Sign::Minus };
+            // -0 is rejected, otherwise zero would be twice as likely
+            if !(magnitude.is_zero() && sign == Sign::Minus) {
+                return BigInt::from((magnitude, sign));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn gen_biguint_test() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(rng.gen_biguint(0), BigUInt::from(0u64));
+        for bits in [1, 63, 64, 65, 200] {
+            let values: Vec<_> = (0..100).map(|_| rng.gen_biguint(bits)).collect();
+            assert!(values.iter().all(|value| value.bit_length() <= bits));
+            // the top bit is set about half of the time
+            assert!(values.iter().any(|value| value.bit_length() == bits));
+            assert!(values.iter().all(|value| value.0.last() != Some(&0)));
+        }
+
+        let bound = (BigUInt::from(1u64) << 64) + 1;
+        for _ in 0..100 {
+            assert!(rng.gen_biguint_below(&bound) < bound);
+        }
+
+        let (low, high) = (BigUInt::from(10u64), BigUInt::from(13u64));
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let value = rng.gen_biguint_range(&low, &high);
+            seen[u64::try_from(&(value - &low)).unwrap() as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[test]
+    fn uniformity_test() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let bound = BigUInt::from(6u64);
+        let mut counts = [0u32; 6];
+        for _ in 0..60_000 {
+            let value = rng.gen_biguint_below(&bound);
+            counts[u64::try_from(&value).unwrap() as usize] += 1;
+        }
+        // chi-squared with 5 degrees of freedom, 20.5 is the 0.999 quantile
+        let chi: f64 = counts
+            .iter()
+            .map(|&count| (f64::from(count) - 10_000.0).powi(2) / 10_000.0)
+            .sum();
+        assert!(chi < 20.5, "{counts:?}");
+
+        let mut counts = [0u32; 7];
+        for _ in 0..70_000 {
+            let value = rng.gen_bigint(2);
+            counts[(i64::try_from(&value).unwrap() + 3) as usize] += 1;
+        }
+        let chi: f64 = counts
+            .iter()
+            .map(|&count| (f64::from(count) - 10_000.0).powi(2) / 10_000.0)
+            .sum();
+        assert!(chi < 22.5, "{counts:?}");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample from an empty range")]
+    fn empty_range_test() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let _ = rng.gen_biguint_below(&BigUInt::from(0u64));
+    }
+}
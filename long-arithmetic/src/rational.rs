@@ -0,0 +1,761 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::bigint::DivRem;
+use crate::convert::{parse_signed, parse_unsigned};
+use crate::{ArithmeticError, ParseIntError};
+
+use super::{BigInt, BigUInt, Pow, Sign};
+
+// invariant - the fraction is reduced, the denominator is positive, zero is 0/1
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct BigRational {
+    numer: BigInt,
+    denom: BigUInt,
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+// how a value is rounded to an integer, `Half*` modes differ only for ties
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    // towards zero
+    Down,
+    // away from zero
+    Up,
+    // towards negative infinity
+    Floor,
+    // towards positive infinity
+    Ceiling,
+    // to the nearest, ties away from zero
+    HalfUp,
+    // to the nearest, ties towards zero
+    HalfDown,
+    // to the nearest, ties to the even neighbour
+    HalfEven,
+}
+
+// numer / denom rounded to an integer, `denom` must not be zero
+pub(crate) fn round_div(numer: &BigInt, denom: &BigUInt, mode: RoundingMode) -> BigInt {
+    let (floor, rem) = numer.div_rem_floor(&BigInt::from(denom.clone()));
+    if rem.inner.is_zero() {
+        return floor;
+    }
+
+    let negative = numer.sign == Sign::Minus;
+    let half = (rem.inner << 1).cmp(denom);
+    let up = match mode {
+        RoundingMode::Floor => false,
+        RoundingMode::Ceiling => true,
+        RoundingMode::Down => negative,
+        RoundingMode::Up => !negative,
+        _ if half != Ordering::Equal => half == Ordering::Greater,
+        RoundingMode::HalfUp => !negative,
+        RoundingMode::HalfDown => negative,
+        RoundingMode::HalfEven => !floor.inner.divisible_by_2(),
+    };
+
+    if up {
+        floor + BigInt::from(1)
+    } else {
+        floor
+    }
+}
+
+// numer * factor
+fn scale(numer: &BigInt, factor: &BigUInt) -> BigInt {
+    BigInt::from((&numer.inner * factor, numer.sign)).fix_zero()
+}
+
+impl BigRational {
+    pub const ZERO: Self = Self {
+        numer: BigInt::ZERO,
+        denom: BigUInt::ONE,
+    };
+    pub const ONE: Self = Self {
+        numer: BigInt::ONE,
+        denom: BigUInt::ONE,
+    };
+
+    // panics if `denom` is zero
+    #[must_use]
+    pub fn new(numer: BigInt, denom: BigInt) -> Self {
+        assert!(!denom.inner.is_zero(), "Division by zero");
+
+        let g = numer.inner.gcd(&denom.inner);
+        let sign = if numer.sign == denom.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        Self {
+            numer: BigInt::from((&numer.inner / &g, sign)).fix_zero(),
+            denom: &denom.inner / &g,
+        }
+    }
+
+    pub fn checked_new(numer: BigInt, denom: BigInt) -> Result<Self, ArithmeticError> {
+        if denom.inner.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(Self::new(numer, denom))
+    }
+
+    #[must_use]
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    // always positive
+    #[must_use]
+    pub fn denom(&self) -> &BigUInt {
+        &self.denom
+    }
+
+    #[must_use]
+    pub fn into_parts(self) -> (BigInt, BigUInt) {
+        (self.numer, self.denom)
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.numer.inner.is_zero()
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1u64
+    }
+
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: BigInt::from(self.numer.inner.clone()),
+            denom: self.denom.clone(),
+        }
+    }
+
+    // 1 / self, panics for zero
+    #[must_use]
+    pub fn recip(&self) -> Self {
+        assert!(!self.is_zero(), "Division by zero");
+        Self {
+            numer: BigInt::from((self.denom.clone(), self.numer.sign)),
+            denom: self.numer.inner.clone(),
+        }
+    }
+
+    pub fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        Ok(self / rhs)
+    }
+
+    // the exact value of a finite float, None for NaN and infinities
+    #[must_use]
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        // value = mantissa * 2^exp
+        let (mantissa, exp, sign) = crate::convert::decode_f64(value);
+
+        if mantissa == 0 {
+            return Some(Self::from(BigInt::from(0)));
+        }
+        if exp >= 0 {
+            let numer = BigUInt::from(mantissa) << exp as u32;
+            return Some(Self::from(BigInt::from((numer, sign))));
+        }
+        // the denominator is a power of two, so only common twos have to be removed
+        let shift = (mantissa.trailing_zeros() as i64).min(-exp);
+        Some(Self {
+            numer: BigInt::from((BigUInt::from(mantissa >> shift), sign)),
+            denom: BigUInt::from(1u64) << (-exp - shift) as u32,
+        })
+    }
+
+    #[must_use]
+    pub fn round_with(&self, mode: RoundingMode) -> BigInt {
+        round_div(&self.numer, &self.denom, mode)
+    }
+
+    #[must_use]
+    pub fn floor(&self) -> BigInt {
+        self.round_with(RoundingMode::Floor)
+    }
+
+    #[must_use]
+    pub fn ceil(&self) -> BigInt {
+        self.round_with(RoundingMode::Ceiling)
+    }
+
+    #[must_use]
+    pub fn trunc(&self) -> BigInt {
+        self.round_with(RoundingMode::Down)
+    }
+
+    // ties away from zero, like `f64::round`
+    #[must_use]
+    pub fn round(&self) -> BigInt {
+        self.round_with(RoundingMode::HalfUp)
+    }
+
+    // [a0; a1, a2, ...] with a0 = floor(self) and positive a1, a2, ...
+    #[must_use]
+    pub fn continued_fraction(&self) -> Vec<BigInt> {
+        let mut numer = self.numer.clone();
+        let mut denom = BigInt::from(self.denom.clone());
+        let mut terms = vec![];
+        loop {
+            let (term, rem) = (&numer).div_rem_floor(&denom);
+            terms.push(term);
+            if rem.inner.is_zero() {
+                return terms;
+            }
+            numer = std::mem::replace(&mut denom, rem);
+        }
+    }
+
+    // value of [a0; a1, a2, ...], terms after the first must be positive
+    #[must_use]
+    pub fn from_continued_fraction(terms: &[BigInt]) -> Self {
+        assert!(
+            !terms.is_empty(),
+            "continued fraction needs at least one term"
+        );
+        convergents(terms).last().unwrap()
+    }
+
+    // successively better approximations [a0], [a0; a1], ... ending with the value itself
+    #[must_use]
+    pub fn convergents(&self) -> Vec<Self> {
+        convergents(&self.continued_fraction()).collect()
+    }
+
+    // the closest fraction with a denominator of at most `max_denom`
+    #[must_use]
+    pub fn limit_denominator(&self, max_denom: &BigUInt) -> Self {
+        assert!(!max_denom.is_zero(), "max_denom must be positive");
+        if self.denom <= *max_denom {
+            return self.clone();
+        }
+
+        // last two convergents within the limit
+        let (mut p0, mut q0) = (BigInt::from(0), BigUInt::from(1u64));
+        let (mut p1, mut q1) = (BigInt::from(1), BigUInt::from(0u64));
+        let mut numer = self.numer.clone();
+        let mut denom = BigInt::from(self.denom.clone());
+        loop {
+            let (term, rem) = (&numer).div_rem_floor(&denom);
+            let q2 = &q0 + &(&term.inner * &q1);
+            if q2 > *max_denom {
+                break;
+            }
+            let p2 = &p0 + &(&term * &p1);
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            numer = std::mem::replace(&mut denom, rem);
+        }
+
+        // the best approximation is either the last convergent or the semiconvergent
+        // with the largest allowed denominator
+        let k = (max_denom - &q0) / &q1;
+        let semi = Self::new(&p0 + &scale(&p1, &k), BigInt::from(&q0 + &(&k * &q1)));
+        let convergent = Self::new(p1, BigInt::from(q1));
+        if (&convergent - self).abs() <= (&semi - self).abs() {
+            convergent
+        } else {
+            semi
+        }
+    }
+}
+
+// h_n / k_n with h_n = a_n * h_(n-1) + h_(n-2) and k_n = a_n * k_(n-1) + k_(n-2)
+fn convergents(terms: &[BigInt]) -> impl Iterator<Item = BigRational> + '_ {
+    let mut h = (BigInt::from(0), BigInt::from(1));
+    let mut k = (BigInt::from(1), BigInt::from(0));
+    terms.iter().map(move |term| {
+        h = (h.1.clone(), term * &h.1 + &h.0);
+        k = (k.1.clone(), term * &k.1 + &k.0);
+        BigRational::new(h.1.clone(), k.1.clone())
+    })
+}
+
+impl From<BigInt> for BigRational {
+    fn from(value: BigInt) -> Self {
+        Self {
+            numer: value,
+            denom: BigUInt::from(1u64),
+        }
+    }
+}
+
+impl From<BigUInt> for BigRational {
+    fn from(value: BigUInt) -> Self {
+        Self::from(BigInt::from(value))
+    }
+}
+
+impl Add<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn add(self, rhs: &BigRational) -> BigRational {
+        if self.denom == rhs.denom {
+            return BigRational::new(&self.numer + &rhs.numer, BigInt::from(self.denom.clone()));
+        }
+        BigRational::new(
+            scale(&self.numer, &rhs.denom) + scale(&rhs.numer, &self.denom),
+            BigInt::from(&self.denom * &rhs.denom),
+        )
+    }
+}
+
+impl Sub<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn sub(self, rhs: &BigRational) -> BigRational {
+        self + &-rhs
+    }
+}
+
+impl Mul<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    // cancelling before multiplying keeps the product reduced
+    fn mul(self, rhs: &BigRational) -> BigRational {
+        // a zero factor is 0/1, so the other denominator cancels out completely
+        let g1 = self.numer.inner.gcd(&rhs.denom);
+        let g2 = rhs.numer.inner.gcd(&self.denom);
+
+        let sign = if self.numer.sign == rhs.numer.sign {
+            Sign::Plus
+        } else {
+            Sign::Minus
+        };
+        let numer = &(&self.numer.inner / &g1) * &(&rhs.numer.inner / &g2);
+        BigRational {
+            numer: BigInt::from((numer, sign)).fix_zero(),
+            denom: &(&self.denom / &g2) * &(&rhs.denom / &g1),
+        }
+    }
+}
+
+impl Div<&BigRational> for &BigRational {
+    type Output = BigRational;
+
+    fn div(self, rhs: &BigRational) -> BigRational {
+        self.mul(&rhs.recip())
+    }
+}
+
+impl Neg for BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> BigRational {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl Neg for &BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> BigRational {
+        -self.clone()
+    }
+}
+
+impl Pow<i64> for BigRational {
+    type Output = Self;
+
+    // negative powers panic for zero
+    fn pow(self, rhs: i64) -> Self {
+        let base = if rhs < 0 { self.recip() } else { self };
+        let exp = rhs.unsigned_abs();
+        Self {
+            numer: base.numer.pow(exp),
+            denom: base.denom.pow(exp),
+        }
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.denom == other.denom {
+            return self.numer.cmp(&other.numer);
+        }
+        scale(&self.numer, &other.denom).cmp(&scale(&other.numer, &self.denom))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigRational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let digits = if self.is_integer() {
+            self.numer.inner.to_string()
+        } else {
+            format!("{}/{}", self.numer.inner, self.denom)
+        };
+        f.pad_integral(self.numer.sign == Sign::Plus, "", &digits)
+    }
+}
+
+// Largest absolute value of the exponent in decimal input like "1e-5". `BigRational`
+// computes 10^exp when parsing, so without a limit a few bytes of input could take
+// minutes. Parsing "1e1000000" takes about 0.1 s in a release build
+pub const MAX_DECIMAL_EXPONENT: u32 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseRationalError {
+    Empty,
+    // byte offset of the first invalid character in the parsed string
+    InvalidDigit { position: usize },
+    ZeroDenominator,
+    // the decimal exponent is above `MAX_DECIMAL_EXPONENT`
+    ExponentOverflow,
+}
+
+impl Display for ParseRationalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRationalError::Empty => write!(f, "cannot parse number from empty string"),
+            ParseRationalError::InvalidDigit { position } => {
+                write!(f, "invalid digit found in string at position {position}")
+            }
+            ParseRationalError::ZeroDenominator => write!(f, "denominator is zero"),
+            ParseRationalError::ExponentOverflow => write!(f, "exponent is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRationalError {}
+
+impl From<ParseIntError> for ParseRationalError {
+    fn from(value: ParseIntError) -> Self {
+        match value {
+            ParseIntError::Empty => ParseRationalError::Empty,
+            ParseIntError::InvalidDigit { position } => {
+                ParseRationalError::InvalidDigit { position }
+            }
+        }
+    }
+}
+
+// [-]digits[.digits][(e|E)[-]digits] as (m, e) with the value m * 10^e,
+// `offset` is the position of `s` in the original string
+pub(crate) fn parse_decimal(s: &str, offset: usize) -> Result<(BigInt, i64), ParseRationalError> {
+    let (negative, unsigned, offset) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest, offset + 1),
+        None => (false, s, offset),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(i) => (&unsigned[..i], Some((&unsigned[i + 1..], offset + i + 1))),
+        None => (unsigned, None),
+    };
+
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if let Some(i) = mantissa.find(|ch: char| !ch.is_ascii_digit() && ch != '.') {
+        return Err(ParseRationalError::InvalidDigit {
+            position: offset + i,
+        });
+    }
+    if let Some(i) = frac.find('.') {
+        return Err(ParseRationalError::InvalidDigit {
+            position: offset + int.len() + 1 + i,
+        });
+    }
+    if int.is_empty() && frac.is_empty() {
+        return Err(ParseRationalError::Empty);
+    }
+
+    let exp = match exponent {
+        None => 0,
+        Some((digits, offset)) => {
+            let exp = parse_signed(digits, 10, offset)?;
+            let abs = u32::try_from(&exp.inner)
+                .ok()
+                .filter(|&abs| abs <= MAX_DECIMAL_EXPONENT)
+                .ok_or(ParseRationalError::ExponentOverflow)?;
+            let abs = i64::from(abs);
+            if exp.sign == Sign::Minus {
+                -abs
+            } else {
+                abs
+            }
+        }
+    };
+
+    let digits = format!("{int}{frac}");
+    let value = parse_unsigned(&digits, 10, offset)?;
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    let value = BigInt::from((value, sign)).fix_zero();
+    Ok((value, exp - frac.len() as i64))
+}
+
+// "a/b", "a" or a decimal number like "-12.5e-3"
+impl FromStr for BigRational {
+    type Err = ParseRationalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let offset = s.len() - s.trim_start().len();
+        let s = s.trim();
+
+        match s.find('/') {
+            Some(slash) => {
+                let numer = parse_signed(&s[..slash], 10, offset)?;
+                let denom = parse_unsigned(&s[slash + 1..], 10, offset + slash + 1)?;
+                if denom.is_zero() {
+                    return Err(ParseRationalError::ZeroDenominator);
+                }
+                Ok(Self::new(numer, BigInt::from(denom)))
+            }
+            None => {
+                let (value, exp) = parse_decimal(s, offset)?;
+                let power = BigInt::from(BigUInt::from(10u64).pow(exp.unsigned_abs()));
+                Ok(if exp >= 0 {
+                    Self::from(value * power)
+                } else {
+                    Self::new(value, power)
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    #[test]
+    fn new_test() {
+        let value = ratio(6, -4);
+        assert_eq!(value.numer(), &BigInt::from(-3));
+        assert_eq!(value.denom(), &BigUInt::from(2u64));
+        assert_eq!(ratio(0, -7), ratio(0, 1));
+        assert_eq!(ratio(-5, -10), ratio(1, 2));
+        assert_eq!(
+            BigRational::checked_new(BigInt::from(1), BigInt::from(0)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (ratio(1, 6), ratio(-3, 4));
+        assert_eq!(&a + &b, ratio(-7, 12));
+        assert_eq!(&a - &b, ratio(11, 12));
+        assert_eq!(&a * &b, ratio(-1, 8));
+        assert_eq!(&a / &b, ratio(-2, 9));
+        assert_eq!(a.clone() + ratio(5, 6), ratio(1, 1));
+        assert_eq!(&a * &ratio(0, 1), ratio(0, 1));
+        assert_eq!(-a.clone(), ratio(-1, 6));
+        assert_eq!(b.recip(), ratio(-4, 3));
+        assert_eq!(b.abs(), ratio(3, 4));
+        assert_eq!(b.clone().pow(-3), ratio(-64, 27));
+        assert_eq!(b.clone().pow(0), ratio(1, 1));
+        assert_eq!(
+            a.checked_div(&ratio(0, 1)),
+            Err(ArithmeticError::DivisionByZero)
+        );
+
+        let mut sum = ratio(0, 1);
+        for n in 1..=20 {
+            sum += ratio(1, n * (n + 1));
+        }
+        assert_eq!(sum, ratio(20, 21));
+    }
+
+    #[test]
+    fn ordering_test() {
+        let mut values = vec![
+            ratio(1, 3),
+            ratio(-1, 2),
+            ratio(2, 7),
+            ratio(0, 1),
+            ratio(-2, 3),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                ratio(-2, 3),
+                ratio(-1, 2),
+                ratio(0, 1),
+                ratio(2, 7),
+                ratio(1, 3)
+            ]
+        );
+        assert!(ratio(1, 3) > ratio(1, 4));
+        assert!(ratio(-1, 3) < ratio(-1, 4));
+    }
+
+    fn parse(s: &str) -> Result<BigRational, ParseRationalError> {
+        s.parse()
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse("3/6"), Ok(ratio(1, 2)));
+        assert_eq!(parse(" -10/4 "), Ok(ratio(-5, 2)));
+        assert_eq!(parse("42"), Ok(ratio(42, 1)));
+        assert_eq!(parse("-0.125"), Ok(ratio(-1, 8)));
+        assert_eq!(parse("1.5e3"), Ok(ratio(1500, 1)));
+        assert_eq!(parse("25E-4"), Ok(ratio(1, 400)));
+        assert_eq!(parse(".5"), Ok(ratio(1, 2)));
+        assert_eq!(parse("2."), Ok(ratio(2, 1)));
+        assert_eq!(parse("-0"), Ok(ratio(0, 1)));
+
+        let err = |s: &str| parse(s).unwrap_err();
+        assert_eq!(err(""), ParseRationalError::Empty);
+        assert_eq!(err("."), ParseRationalError::Empty);
+        assert_eq!(err("1/"), ParseRationalError::Empty);
+        assert_eq!(err("1/0"), ParseRationalError::ZeroDenominator);
+        assert_eq!(
+            err("12/3x"),
+            ParseRationalError::InvalidDigit { position: 4 }
+        );
+        assert_eq!(
+            err("1.2.3"),
+            ParseRationalError::InvalidDigit { position: 3 }
+        );
+        assert_eq!(
+            err(" 1.5q"),
+            ParseRationalError::InvalidDigit { position: 4 }
+        );
+        assert_eq!(
+            err("1e5x"),
+            ParseRationalError::InvalidDigit { position: 3 }
+        );
+        assert_eq!(err("1e99999999999"), ParseRationalError::ExponentOverflow);
+        assert_eq!(err("1e2000000000"), ParseRationalError::ExponentOverflow);
+        assert_eq!(err("1e-1000001"), ParseRationalError::ExponentOverflow);
+        assert_eq!(
+            "5e-1000".parse::<BigRational>().unwrap(),
+            BigRational::new(BigInt::from(1), BigInt::from(2) * BigInt::from(10).pow(999))
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(ratio(-5, 2).to_string(), "-5/2");
+        assert_eq!(ratio(4, 2).to_string(), "2");
+        assert_eq!(format!("{:>6}", ratio(1, 3)), "   1/3");
+        assert_eq!(format!("{:+}", ratio(1, 3)), "+1/3");
+    }
+
+    #[test]
+    fn from_f64_test() {
+        assert_eq!(BigRational::from_f64(0.5), Some(ratio(1, 2)));
+        assert_eq!(BigRational::from_f64(-3.0), Some(ratio(-3, 1)));
+        assert_eq!(BigRational::from_f64(-0.0), Some(ratio(0, 1)));
+        assert_eq!(
+            BigRational::from_f64(0.1),
+            Some(ratio(3_602_879_701_896_397, 36_028_797_018_963_968))
+        );
+        assert_eq!(
+            BigRational::from_f64(1e300).unwrap().to_string(),
+            format!("{:.0}", 1e300)
+        );
+        let tiny = BigRational::from_f64(f64::from_bits(1)).unwrap();
+        assert_eq!(tiny.denom(), &(BigUInt::from(1u64) << 1074));
+        assert_eq!(BigRational::from_f64(f64::NAN), None);
+        assert_eq!(BigRational::from_f64(f64::NEG_INFINITY), None);
+    }
+
+    #[test]
+    fn rounding_test() {
+        use RoundingMode::*;
+        let modes = [Down, Up, Floor, Ceiling, HalfUp, HalfDown, HalfEven];
+        // value in tenths and the expected results in the order of `modes`
+        for (tenths, expected) in [
+            (25, [2, 3, 2, 3, 3, 2, 2]),
+            (35, [3, 4, 3, 4, 4, 3, 4]),
+            (24, [2, 3, 2, 3, 2, 2, 2]),
+            (26, [2, 3, 2, 3, 3, 3, 3]),
+            (-25, [-2, -3, -3, -2, -3, -2, -2]),
+            (-26, [-2, -3, -3, -2, -3, -3, -3]),
+            (-24, [-2, -3, -3, -2, -2, -2, -2]),
+            (30, [3, 3, 3, 3, 3, 3, 3]),
+            (-5, [0, -1, -1, 0, -1, 0, 0]),
+        ] {
+            let value = ratio(tenths, 10);
+            for (mode, expected) in modes.into_iter().zip(expected) {
+                assert_eq!(
+                    value.round_with(mode),
+                    BigInt::from(expected),
+                    "{value} {mode:?}"
+                );
+            }
+        }
+        assert_eq!(ratio(-7, 2).floor(), BigInt::from(-4));
+        assert_eq!(ratio(-7, 2).ceil(), BigInt::from(-3));
+        assert_eq!(ratio(-7, 2).trunc(), BigInt::from(-3));
+        assert_eq!(ratio(-7, 2).round(), BigInt::from(-4));
+    }
+
+    #[test]
+    fn continued_fraction_test() {
+        let value = ratio(415, 93);
+        let terms = value.continued_fraction();
+        assert_eq!(terms, [4, 2, 6, 7].map(BigInt::from));
+        assert_eq!(BigRational::from_continued_fraction(&terms), value);
+        assert_eq!(
+            value.convergents(),
+            vec![ratio(4, 1), ratio(9, 2), ratio(58, 13), value.clone()]
+        );
+
+        let negative = ratio(-415, 93);
+        let terms = negative.continued_fraction();
+        assert_eq!(terms[0], BigInt::from(-5));
+        assert_eq!(BigRational::from_continued_fraction(&terms), negative);
+    }
+
+    #[test]
+    fn limit_denominator_test() {
+        let pi = BigRational::from_f64(std::f64::consts::PI).unwrap();
+        // same results as Python's `Fraction.limit_denominator`
+        for (max, expected) in [
+            (1u64, ratio(3, 1)),
+            (10, ratio(22, 7)),
+            (100, ratio(311, 99)),
+            (1000, ratio(355, 113)),
+            (100_000, ratio(312_689, 99_532)),
+        ] {
+            assert_eq!(pi.limit_denominator(&BigUInt::from(max)), expected);
+        }
+        assert_eq!(
+            (-pi).limit_denominator(&BigUInt::from(1000u64)),
+            ratio(-355, 113)
+        );
+        assert_eq!(
+            ratio(1, 3).limit_denominator(&BigUInt::from(5u64)),
+            ratio(1, 3)
+        );
+        assert_eq!(
+            "0.333"
+                .parse::<BigRational>()
+                .unwrap()
+                .limit_denominator(&BigUInt::from(10u64)),
+            ratio(1, 3)
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use super::{ArithmeticError, BigInt, BigUInt, Pow, Sign};
+
+// roots up to this size are computed directly from a floating point estimate
+const DIRECT_ROOT_BITS: u32 = 128;
+
+// x' = ((n - 1) * x + value / x^(n - 1)) / n
+fn newton_step(value: &BigUInt, n: u32, x: &BigUInt) -> BigUInt {
+    (x.clone() * (n as u64 - 1) + value / &x.clone().pow(n as u64 - 1)) / n as u64
+}
+
+// starting from above the root the iteration decreases until it reaches the floor root.
+// Seeds below the root are avoided: for large `n` the first step would overshoot enormously
+fn newton(value: &BigUInt, n: u32, seed: BigUInt) -> BigUInt {
+    let mut x = seed;
+    loop {
+        let next = newton_step(value, n, &x);
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+// 2^(log2(value) / n) rounded up with ~30 correct bits, value must not be zero
+fn estimate(value: &BigUInt, n: u32) -> BigUInt {
+    let bits = value.bits_num();
+    let shift = bits.saturating_sub(64);
+    let top = u64::try_from(&(value >> shift)).unwrap();
+    let log = ((top as f64).log2() + shift as f64) / n as f64;
+
+    // margin for the rounding errors of the floating point computation
+    let exp = log.floor() as u32;
+    let mantissa = (log.fract().exp2() * (1u64 << 52) as f64) as u64 + (1 << 22);
+    if exp >= 52 {
+        BigUInt::from(mantissa + 1) << (exp - 52)
+    } else {
+        BigUInt::from((mantissa >> (52 - exp)) + 1)
+    }
+}
+
+// floor(value^(1/n)) for n >= 2
+fn root(value: &BigUInt, n: u32) -> BigUInt {
+    let bits = value.bits_num();
+    if value.is_zero() {
+        return BigUInt::from(0u64);
+    }
+    // value < 2^n
+    if bits <= n {
+        return BigUInt::from(1u64);
+    }
+    if bits / n <= DIRECT_ROOT_BITS {
+        return newton(value, n, estimate(value, n));
+    }
+
+    // the root of the upper half of bits gives the upper half of the root's bits,
+    // so a couple of Newton steps at full precision are enough
+    let k = bits / (2 * n);
+    let high = root(&(value >> (n * k)), n);
+    newton(value, n, (high + 1) << k)
+}
+
+impl BigUInt {
+    // floor(sqrt(self))
+    #[must_use]
+    pub fn sqrt(&self) -> Self {
+        root(self, 2)
+    }
+
+    // floor(cbrt(self))
+    #[must_use]
+    pub fn cbrt(&self) -> Self {
+        root(self, 3)
+    }
+
+    // floor(self^(1/n))
+    #[must_use]
+    pub fn nth_root(&self, n: u32) -> Self {
+        match n {
+            0 => panic!("zeroth root is undefined"),
+            1 => self.clone(),
+            _ => root(self, n),
+        }
+    }
+
+    // (s, r) such that self = s^2 + r and s = floor(sqrt(self))
+    #[must_use]
+    pub fn sqrt_rem(&self) -> (Self, Self) {
+        let sqrt = self.sqrt();
+        let rem = self - &(&sqrt * &sqrt);
+        (sqrt, rem)
+    }
+}
+
+impl BigInt {
+    // `NegativeOperand` for negative numbers
+    pub fn checked_sqrt(&self) -> Result<Self, ArithmeticError> {
+        self.checked_nth_root(2)
+    }
+
+    // cube root of a negative number is negative
+    pub fn checked_cbrt(&self) -> Result<Self, ArithmeticError> {
+        self.checked_nth_root(3)
+    }
+
+    // `ZerothRoot` for n = 0 and `NegativeOperand` for even roots of negative numbers,
+    // odd roots are rounded towards zero
+    pub fn checked_nth_root(&self, n: u32) -> Result<Self, ArithmeticError> {
+        if n == 0 {
+            return Err(ArithmeticError::ZerothRoot);
+        }
+        if self.sign == Sign::Minus && n.is_multiple_of(2) {
+            return Err(ArithmeticError::NegativeOperand);
+        }
+        Ok(Self::from((self.inner.nth_root(n), self.sign)).fix_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::limbs;
+
+    fn check_root(value: &BigUInt, n: u32) {
+        let root = value.nth_root(n);
+        assert!(root.clone().pow(n as u64) <= *value);
+        assert!((root + 1).pow(n as u64) > *value);
+    }
+
+    #[test]
+    fn small_roots_test() {
+        for value in 0..3000u64 {
+            for n in 1..6 {
+                check_root(&BigUInt::from(value), n);
+            }
+        }
+        assert_eq!(
+            BigUInt::from(u64::MAX).sqrt(),
+            BigUInt::from(u32::MAX as u64)
+        );
+        assert_eq!(BigUInt::from(27u64).cbrt(), BigUInt::from(3u64));
+        assert_eq!(BigUInt::from(26u64).cbrt(), BigUInt::from(2u64));
+    }
+
+    #[test]
+    fn large_roots_test() {
+        for len in [2, 5, 17, 60] {
+            let value = BigUInt::from(limbs(len as u64, len));
+            for n in [2, 3, 5, 7, 64, 1000] {
+                check_root(&value, n);
+            }
+        }
+
+        // exact powers and their neighbours
+        let base = BigUInt::from(limbs(3, 40));
+        let square = &base * &base;
+        assert_eq!(square.sqrt(), base);
+        assert_eq!((square.clone() - 1).sqrt(), base.clone() - 1);
+        assert_eq!((&base * &square).cbrt(), base);
+        assert_eq!((&base * &square - 1).cbrt(), base.clone() - 1);
+        assert_eq!(base.clone().pow(5).nth_root(5), base);
+    }
+
+    #[test]
+    fn sqrt_rem_test() {
+        assert_eq!(
+            BigUInt::from(0u64).sqrt_rem(),
+            (BigUInt::from(0u64), BigUInt::from(0u64))
+        );
+        assert_eq!(
+            BigUInt::from(30u64).sqrt_rem(),
+            (BigUInt::from(5u64), BigUInt::from(5u64))
+        );
+
+        let value = BigUInt::from(limbs(9, 33));
+        let (sqrt, rem) = value.sqrt_rem();
+        assert_eq!(&sqrt * &sqrt + &rem, value);
+        assert!(rem <= sqrt.clone() << 1);
+    }
+
+    #[test]
+    fn bigint_roots_test() {
+        let negative = Err(ArithmeticError::NegativeOperand);
+        assert_eq!(BigInt::from(-4).checked_sqrt(), negative);
+        assert_eq!(BigInt::from(17).checked_sqrt(), Ok(BigInt::from(4)));
+        assert_eq!(BigInt::from(-27).checked_cbrt(), Ok(BigInt::from(-3)));
+        assert_eq!(BigInt::from(-26).checked_cbrt(), Ok(BigInt::from(-2)));
+        assert_eq!(BigInt::from(-1).checked_nth_root(4), negative);
+        assert_eq!(BigInt::from(-1).checked_nth_root(5), Ok(BigInt::from(-1)));
+        assert_eq!(BigInt::from(0).checked_nth_root(2), Ok(BigInt::from(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "zeroth root")]
+    fn zeroth_root_test() {
+        let _ = BigUInt::from(5u64).nth_root(0);
+    }
+}
@@ -0,0 +1,102 @@
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+use super::{BigInt, BigUInt, Sign};
+
+// Human-readable formats like JSON get decimal strings, which other languages can parse
+// without losing precision. Binary formats get the little-endian limbs, and `BigInt`
+// also a flag that is set for negative numbers
+
+fn trimmed(mut limbs: Vec<u64>) -> BigUInt {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    BigUInt::from(limbs)
+}
+
+impl Serialize for BigUInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.as_slice().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BigUInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(D::Error::custom)
+        } else {
+            Ok(trimmed(Vec::deserialize(deserializer)?))
+        }
+    }
+}
+
+impl Serialize for BigInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.sign == Sign::Minus, self.inner.0.as_slice()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(D::Error::custom)
+        } else {
+            let (negative, limbs) = <(bool, Vec<u64>)>::deserialize(deserializer)?;
+            let sign = if negative { Sign::Minus } else { Sign::Plus };
+            Ok(BigInt::from((trimmed(limbs), sign)).fix_zero())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_test() {
+        let value = BigUInt::from(vec![0x42B6_5689_328B_BE0C, 0x29D]);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"12345678910111213141516\"");
+        assert_eq!(serde_json::from_str::<BigUInt>(&json).unwrap(), value);
+
+        let negative = -BigInt::from(value);
+        let json = serde_json::to_string(&negative).unwrap();
+        assert_eq!(json, "\"-12345678910111213141516\"");
+        assert_eq!(serde_json::from_str::<BigInt>(&json).unwrap(), negative);
+
+        let error = serde_json::from_str::<BigUInt>("\"12a\"").unwrap_err();
+        assert!(error.to_string().contains("invalid digit"), "{error}");
+        assert!(serde_json::from_str::<BigInt>("12").is_err());
+    }
+
+    #[test]
+    fn binary_test() {
+        let value = BigUInt::from(vec![1, 2, 3]);
+        let bytes = bincode::serialize(&value).unwrap();
+        // length followed by the limbs
+        assert_eq!(bytes.len(), 8 + 3 * 8);
+        assert_eq!(bincode::deserialize::<BigUInt>(&bytes).unwrap(), value);
+
+        for value in [BigInt::from(0), BigInt::from(-5), -(BigInt::from(1) << 200)] {
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_eq!(bincode::deserialize::<BigInt>(&bytes).unwrap(), value);
+        }
+
+        // leading zero limbs and negative zero are normalized
+        let bytes = bincode::serialize(&(true, vec![0u64, 0])).unwrap();
+        let zero = bincode::deserialize::<BigInt>(&bytes).unwrap();
+        assert_eq!((zero.inner.0.len(), zero.sign), (0, Sign::Plus));
+    }
+}
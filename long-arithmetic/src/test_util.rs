@@ -0,0 +1,17 @@
+// helpers shared by the test modules
+
+// `len` pseudo-random limbs from a deterministic xorshift, so tests don't need
+// an rng dependency. the top limb is odd, so the value has exactly `len` limbs
+pub(crate) fn limbs(mut seed: u64, len: usize) -> Vec<u64> {
+    let mut limbs = Vec::with_capacity(len);
+    for _ in 0..len {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        limbs.push(seed);
+    }
+    if let Some(last) = limbs.last_mut() {
+        *last |= 1;
+    }
+    limbs
+}
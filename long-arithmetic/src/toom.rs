@@ -0,0 +1,177 @@
+use crate::helpers::add_shifted;
+use crate::mul::{map_parallel, mul, square};
+
+use super::{BigInt, BigUInt, Sign};
+
+// splits limbs into three parts of `size` limbs, the last one may be shorter
+fn split3(limbs: &[u64], size: usize) -> [BigUInt; 3] {
+    let part = |n: usize| {
+        let start = (n * size).min(limbs.len());
+        let end = ((n + 1) * size).min(limbs.len());
+        let mut part = limbs[start..end].to_vec();
+        while part.last() == Some(&0) {
+            let _ = part.pop();
+        }
+        BigUInt::from(part)
+    };
+
+    [part(0), part(1), part(2)]
+}
+
+// exact division by 3
+fn div3(value: BigInt) -> BigInt {
+    let (div, rem) = value.inner.div_rem_u64(3);
+    debug_assert_eq!(rem, 0, "division by 3 is not exact");
+
+    BigInt::from((div, value.sign)).fix_zero()
+}
+
+fn signed_mul(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+    let sign = if lhs.sign == rhs.sign {
+        Sign::Plus
+    } else {
+        Sign::Minus
+    };
+
+    BigInt::from((mul(&lhs.inner.0, &rhs.inner.0), sign)).fix_zero()
+}
+
+// values of a0 + a1 * x + a2 * x^2 at points 0, 1, -1, -2 and infinity
+fn evaluate([a0, a1, a2]: [BigUInt; 3]) -> [BigInt; 5] {
+    let a0 = BigInt::from(a0);
+    let a1 = BigInt::from(a1);
+    let a2 = BigInt::from(a2);
+
+    let tmp = &a0 + &a2;
+    let at_1 = &tmp + &a1;
+    let at_m1 = tmp - &a1;
+    let at_m2 = ((&at_m1 + &a2) << 1) - &a0;
+
+    [a0, at_1, at_m1, at_m2, a2]
+}
+
+// Toom-Cook 3-way multiplication, O(n^log(3,5)), with Bodrato's interpolation sequence
+pub fn toom3_mul(rhs: BigUInt, lhs: BigUInt) -> BigUInt {
+    toom3(&rhs.0, &lhs.0)
+}
+
+// operands must not have leading zeros
+pub(crate) fn toom3(rhs: &[u64], lhs: &[u64]) -> BigUInt {
+    let size = rhs.len().max(lhs.len()).div_ceil(3);
+    if size == 0 {
+        return BigUInt::from(0u64);
+    }
+
+    let [r0, r1, rm1, rm2, rinf] = evaluate(split3(rhs, size));
+    let [l0, l1, lm1, lm2, linf] = evaluate(split3(lhs, size));
+
+    let points = map_parallel(
+        rhs.len().min(lhs.len()),
+        [(r0, l0), (r1, l1), (rm1, lm1), (rm2, lm2), (rinf, linf)],
+        |(r, l)| signed_mul(&r, &l),
+    );
+
+    interpolate(points, size, rhs.len() + lhs.len())
+}
+
+// squares of evaluations are non-negative, so every product is a plain square
+pub(crate) fn toom3_square(limbs: &[u64]) -> BigUInt {
+    let size = limbs.len().div_ceil(3);
+    if size == 0 {
+        return BigUInt::from(0u64);
+    }
+
+    let points = map_parallel(limbs.len(), evaluate(split3(limbs, size)), |point| {
+        BigInt::from(square(&point.inner.0))
+    });
+    interpolate(points, size, 2 * limbs.len())
+}
+
+// recovers the product from its values at 0, 1, -1, -2 and infinity
+fn interpolate([w0, w1, wm1, wm2, winf]: [BigInt; 5], size: usize, len: usize) -> BigUInt {
+    let mut t3 = div3(wm2 - &w1);
+    let mut t1 = (w1 - &wm1) >> 1;
+    let mut t2 = wm1 - &w0;
+    t3 = ((&t2 - t3) >> 1) + (&winf << 1);
+    t2 = t2 + &t1 - &winf;
+    t1 -= &t3;
+
+    // all coefficients of the product are non-negative
+    let mut result = w0.inner;
+    result.0.reserve(len.saturating_sub(result.0.len()));
+    for (i, coefficient) in [t1, t2, t3, winf].into_iter().enumerate() {
+        debug_assert_eq!(coefficient.sign, Sign::Plus);
+        add_shifted(&mut result, &coefficient.inner.0, (i + 1) * size);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mul::schoolbook_mul;
+
+    #[test]
+    fn split3_test() {
+        assert_eq!(
+            split3(&[1, 2, 3, 4, 5, 6, 7], 3),
+            [
+                BigUInt::from(vec![1, 2, 3]),
+                BigUInt::from(vec![4, 5, 6]),
+                BigUInt::from(vec![7])
+            ]
+        );
+        assert_eq!(
+            split3(&[1, 0, 0, 4], 2),
+            [
+                BigUInt::from(vec![1]),
+                BigUInt::from(vec![0, 4]),
+                BigUInt::from(0u64)
+            ]
+        );
+    }
+
+    #[test]
+    fn div3_test() {
+        assert_eq!(div3(BigInt::from(-369)), BigInt::from(-123));
+        assert_eq!(div3(BigInt::from(0)), BigInt::from(0));
+        assert_eq!(
+            div3(BigInt::from(vec![u64::MAX, u64::MAX])),
+            BigInt::from(vec![0x5555_5555_5555_5555, 0x5555_5555_5555_5555])
+        );
+    }
+
+    #[test]
+    fn toom3_test() {
+        let left = BigUInt::from(123u64);
+        let right = BigUInt::from(0u64);
+        assert_eq!(toom3_mul(left.clone(), right.clone()), left * right);
+
+        let left = BigUInt::from(u64::MAX);
+        let right = BigUInt::from(u64::MAX);
+        assert_eq!(toom3_mul(left.clone(), right.clone()), left * right);
+
+        let left = BigUInt::from(vec![0xFFFF_FFFF_FFFF_FFFF, 0x1111_1111_1111_1111, 0x3333]);
+        let right = BigUInt::from(vec![0xFFFF_FFFF_FFFF_FFFF, 0x2222_2222_2222_2222, 0x3456]);
+        assert_eq!(toom3_mul(left.clone(), right.clone()), left * right);
+
+        let left = BigUInt::from(vec![u64::MAX; 50]);
+        let right = BigUInt::from(vec![u64::MAX; 31]);
+        assert_eq!(
+            toom3_mul(left.clone(), right.clone()),
+            schoolbook_mul(left, right)
+        );
+
+        let left = BigUInt::from(
+            (1..=40)
+                .map(|x| x * 0x0123_4567_89AB_CDEF)
+                .collect::<Vec<_>>(),
+        );
+        let right = BigUInt::from(vec![0, 0, 0, 0, 0, 0, 1, 0, 0, u64::MAX]);
+        assert_eq!(
+            toom3_mul(left.clone(), right.clone()),
+            schoolbook_mul(left, right)
+        );
+    }
+}
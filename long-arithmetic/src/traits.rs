@@ -0,0 +1,310 @@
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedEuclid, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Euclid,
+    FromPrimitive, Num, One, Signed, ToPrimitive, Unsigned, Zero,
+};
+
+use super::{BigInt, BigUInt, ParseIntError, Sign};
+
+// implementations of the `num-traits` interfaces on top of the inherent methods
+
+impl Zero for BigUInt {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        BigUInt::is_zero(self)
+    }
+}
+
+impl One for BigUInt {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl Zero for BigInt {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.inner.is_zero()
+    }
+}
+
+impl One for BigInt {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl Num for BigUInt {
+    type FromStrRadixErr = ParseIntError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+        BigUInt::from_str_radix(s, radix)
+    }
+}
+
+impl Num for BigInt {
+    type FromStrRadixErr = ParseIntError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+        BigInt::from_str_radix(s, radix)
+    }
+}
+
+impl Unsigned for BigUInt {}
+
+impl Signed for BigInt {
+    fn abs(&self) -> Self {
+        Self::from(self.inner.clone())
+    }
+
+    // self - other if it is positive, zero otherwise
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            Self::zero()
+        }
+    }
+
+    fn signum(&self) -> Self {
+        match (self.sign, self.inner.is_zero()) {
+            (_, true) => Self::zero(),
+            (Sign::Plus, false) => Self::one(),
+            (Sign::Minus, false) => Self::from(-1),
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        self.sign == Sign::Plus && !self.inner.is_zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign == Sign::Minus
+    }
+}
+
+macro_rules! impl_pow {
+    ($t:ty, $($exp:ty),*) => {$(
+        impl num_traits::Pow<$exp> for $t {
+            type Output = $t;
+
+            fn pow(self, rhs: $exp) -> $t {
+                crate::Pow::pow(self, u64::from(rhs))
+            }
+        }
+
+        impl num_traits::Pow<$exp> for &$t {
+            type Output = $t;
+
+            fn pow(self, rhs: $exp) -> $t {
+                crate::Pow::pow(self.clone(), u64::from(rhs))
+            }
+        }
+    )*};
+}
+
+impl_pow!(BigUInt, u8, u16, u32, u64);
+impl_pow!(BigInt, u8, u16, u32, u64);
+
+macro_rules! impl_checked {
+    ($t:ty) => {
+        impl CheckedAdd for $t {
+            fn checked_add(&self, v: &Self) -> Option<Self> {
+                Some(self + v)
+            }
+        }
+
+        impl CheckedMul for $t {
+            fn checked_mul(&self, v: &Self) -> Option<Self> {
+                Some(self * v)
+            }
+        }
+
+        impl CheckedDiv for $t {
+            fn checked_div(&self, v: &Self) -> Option<Self> {
+                <$t>::checked_div(self, v).ok()
+            }
+        }
+
+        impl CheckedRem for $t {
+            fn checked_rem(&self, v: &Self) -> Option<Self> {
+                <$t>::checked_rem(self, v).ok()
+            }
+        }
+    };
+}
+
+impl_checked!(BigUInt);
+impl_checked!(BigInt);
+
+impl CheckedSub for BigUInt {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        BigUInt::checked_sub(self, v).ok()
+    }
+}
+
+impl CheckedSub for BigInt {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        Some(self - v)
+    }
+}
+
+impl CheckedNeg for BigInt {
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-self.clone())
+    }
+}
+
+impl Euclid for BigInt {
+    fn div_euclid(&self, v: &Self) -> Self {
+        BigInt::div_euclid(self, v)
+    }
+
+    fn rem_euclid(&self, v: &Self) -> Self {
+        BigInt::rem_euclid(self, v)
+    }
+}
+
+impl CheckedEuclid for BigInt {
+    fn checked_div_euclid(&self, v: &Self) -> Option<Self> {
+        (!v.inner.is_zero()).then(|| BigInt::div_euclid(self, v))
+    }
+
+    fn checked_rem_euclid(&self, v: &Self) -> Option<Self> {
+        (!v.inner.is_zero()).then(|| BigInt::rem_euclid(self, v))
+    }
+}
+
+macro_rules! impl_primitive {
+    ($t:ty) => {
+        impl ToPrimitive for $t {
+            fn to_i64(&self) -> Option<i64> {
+                i64::try_from(self).ok()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                u64::try_from(self).ok()
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                i128::try_from(self).ok()
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                u128::try_from(self).ok()
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some(<$t>::to_f32(self))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some(<$t>::to_f64(self))
+            }
+        }
+
+        // floats are truncated towards zero like `as` casts
+        impl FromPrimitive for $t {
+            fn from_i64(n: i64) -> Option<Self> {
+                Self::from_i128(i128::from(n))
+            }
+
+            fn from_u64(n: u64) -> Option<Self> {
+                Some(Self::from(n))
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                Self::try_from(BigInt::from(n)).ok()
+            }
+
+            fn from_u128(n: u128) -> Option<Self> {
+                Some(Self::from(n))
+            }
+
+            fn from_f64(n: f64) -> Option<Self> {
+                Self::try_from(n.trunc()).ok()
+            }
+        }
+    };
+}
+
+impl_primitive!(BigUInt);
+impl_primitive!(BigInt);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // only uses the traits, like code that is generic over integer types
+    fn sum_of_powers<T: Num + Clone + num_traits::Pow<u32, Output = T>>(n: u32, exp: u32) -> T {
+        let mut sum = T::zero();
+        let mut k = T::one();
+        for _ in 0..n {
+            sum = sum + k.clone().pow(exp);
+            k = k + T::one();
+        }
+        sum
+    }
+
+    #[test]
+    fn generic_test() {
+        assert_eq!(sum_of_powers::<BigUInt>(10, 3), BigUInt::from(3025u64));
+        assert_eq!(sum_of_powers::<BigInt>(100, 2), BigInt::from(338_350));
+        assert_eq!(
+            <BigInt as Num>::from_str_radix("-ff", 16),
+            Ok(BigInt::from(-255))
+        );
+        assert!(Zero::is_zero(&BigUInt::zero()));
+        assert!(BigInt::one().is_one());
+    }
+
+    #[test]
+    fn signed_test() {
+        assert_eq!(Signed::abs(&BigInt::from(-5)), BigInt::from(5));
+        assert_eq!(BigInt::from(3).abs_sub(&BigInt::from(5)), BigInt::from(0));
+        assert_eq!(BigInt::from(5).abs_sub(&BigInt::from(3)), BigInt::from(2));
+        assert_eq!(BigInt::from(-7).signum(), BigInt::from(-1));
+        assert_eq!(BigInt::from(0).signum(), BigInt::from(0));
+        assert!(BigInt::from(2).is_positive() && !BigInt::from(0).is_positive());
+        assert!(BigInt::from(-2).is_negative() && !BigInt::from(0).is_negative());
+    }
+
+    #[test]
+    fn checked_test() {
+        let (a, b) = (BigUInt::from(3u64), BigUInt::from(5u64));
+        assert_eq!(CheckedSub::checked_sub(&a, &b), None);
+        assert_eq!(CheckedSub::checked_sub(&b, &a), Some(BigUInt::from(2u64)));
+        assert_eq!(CheckedDiv::checked_div(&a, &BigUInt::zero()), None);
+        assert_eq!(CheckedRem::checked_rem(&b, &a), Some(BigUInt::from(2u64)));
+        assert_eq!(a.checked_add(&b), Some(BigUInt::from(8u64)));
+        assert_eq!(a.checked_mul(&b), Some(BigUInt::from(15u64)));
+
+        let (a, b) = (BigInt::from(-7), BigInt::from(2));
+        assert_eq!(CheckedSub::checked_sub(&b, &a), Some(BigInt::from(9)));
+        assert_eq!(a.checked_neg(), Some(BigInt::from(7)));
+        assert_eq!(Euclid::div_euclid(&a, &b), BigInt::from(-4));
+        assert_eq!(Euclid::rem_euclid(&a, &b), BigInt::from(1));
+        assert_eq!(a.checked_rem_euclid(&BigInt::zero()), None);
+    }
+
+    #[test]
+    fn primitive_test() {
+        let big = BigUInt::from(u128::MAX);
+        assert_eq!(big.to_u128(), Some(u128::MAX));
+        assert_eq!(big.to_u64(), None);
+        assert_eq!(ToPrimitive::to_f64(&big), Some(u128::MAX as f64));
+        assert_eq!(BigInt::from(-3).to_i8(), Some(-3));
+        assert_eq!(BigInt::from(-3).to_u32(), None);
+
+        assert_eq!(BigUInt::from_i64(-1), None);
+        assert_eq!(BigUInt::from_i32(12), Some(BigUInt::from(12u64)));
+        assert_eq!(BigInt::from_f64(-2.7), Some(BigInt::from(-2)));
+        assert_eq!(BigInt::from_f64(f64::NAN), None);
+        assert_eq!(BigUInt::from_f64(-0.5), Some(BigUInt::from(0u64)));
+    }
+}